@@ -3,3 +3,7 @@ use bevy::prelude::*;
 // Marker component for tracking the camera
 #[derive(Component)]
 pub struct MainCamera;
+
+/// how far the player has zoomed the camera, the projection scale before any transition's zoom
+#[derive(Component)]
+pub struct CameraZoom(pub f32);
@@ -1,8 +1,12 @@
-use crate::camera::camera_systems::{follow_player, spawn_camera};
+use crate::camera::camera_systems::{
+    follow_player, scale_camera, spawn_camera, toggle_fullscreen, update_ambient_light, zoom_camera,
+};
+use crate::schedule::InGameSet;
+use crate::state::GameState;
 use bevy::{color::palettes::tailwind::GRAY_200, prelude::*};
 use bevy_lit::prelude::Lighting2dPlugin;
 
-/// a 2D camera that follows the player perpendicularly
+/// a 2D camera that follows the player perpendicularly and zooms, and the window it shows in
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
@@ -10,6 +14,17 @@ impl Plugin for CameraPlugin {
         app.add_plugins(Lighting2dPlugin)
             .insert_resource(ClearColor(Color::from(GRAY_200)))
             .add_systems(Startup, spawn_camera)
-            .add_systems(Update, follow_player);
+            .add_systems(
+                Update,
+                zoom_camera
+                    .in_set(InGameSet::Update)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                ((scale_camera, follow_player).chain(), update_ambient_light)
+                    .in_set(InGameSet::Render),
+            )
+            .add_systems(Update, toggle_fullscreen);
     }
 }
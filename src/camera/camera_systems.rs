@@ -1,18 +1,37 @@
-use crate::{camera::camera_component::MainCamera, constants::CAMERA_LAYER};
-use bevy::{color::palettes::tailwind::BLUE_300, prelude::*};
+use crate::{
+    camera::camera_component::{CameraZoom, MainCamera},
+    constants::{CAMERA_LAYER, ROOM_HEIGHT},
+    controls::Action,
+    floorplan::RoomHealth,
+    room::room_component::RoomState,
+    settings::{CameraSettings, Settings},
+    state::{GameState, state_component::FadeEffect},
+};
+use bevy::{
+    color::palettes::tailwind::{AMBER_200, BLUE_300, GREEN_300, RED_300},
+    prelude::*,
+    render::camera::ScalingMode,
+    window::{PrimaryWindow, WindowMode},
+};
 use bevy_lit::prelude::{AmbientLight2d, Lighting2dSettings, RaymarchSettings};
-
-const CAMERA_MOVE_SPEED: f32 = 10.0; // Speed at which the camera moves
-const SCREEN_HALF_WIDTH: f32 = 600.0; // Half of window width (assuming 1200x800 resolution)
-const SCROLL_THRESHOLD: f32 = 400.0; // Distance from the screen edge before scrolling
+use leafwing_input_manager::prelude::ActionState;
 
 type PlayerQuery<'a> = Query<'a, 'a, &'a Transform, With<crate::player::Player>>;
-type CameraQuery<'a> = Query<'a, 'a, &'a mut Transform, With<MainCamera>>;
+type CameraQuery<'a> =
+    Query<'a, 'a, (&'a mut Transform, &'a OrthographicProjection), With<MainCamera>>;
 
+/// the room fits the height of the window whatever its size, a wider window shows more of it
 pub fn spawn_camera(mut commands: Commands) {
     commands.spawn((
         Camera2d,
         MainCamera, // Mark the camera for easy querying
+        CameraZoom(1.0),
+        OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical {
+                viewport_height: ROOM_HEIGHT,
+            },
+            ..OrthographicProjection::default_2d()
+        },
         Lighting2dSettings {
             blur: 32.,
             raymarch: RaymarchSettings {
@@ -24,29 +43,221 @@ pub fn spawn_camera(mut commands: Commands) {
         },
         AmbientLight2d {
             brightness: 0.5,
-            color: Color::from(BLUE_300),
+            color: ambient_color(RoomHealth::Unknown),
         },
     ));
 }
 
-pub fn follow_player(mut query_set: ParamSet<(PlayerQuery<'_>, CameraQuery<'_>)>) {
-    if let Ok(player_transform) = query_set.p0().get_single() {
-        let player_x = player_transform.translation.x;
+/**
+ * where the camera is headed - just far enough to keep the player in the deadzone, but never so
+ * far that the view shows past the walls, floor or ceiling, and centered on a room smaller than
+ * the view
+ */
+pub fn camera_target(camera: Vec2, player: Vec2, deadzone: Vec2, room: Rect, view: Vec2) -> Vec2 {
+    let follow = camera.clamp(player - deadzone, player + deadzone);
+    let min = room.min + view;
+    let max = room.max - view;
+    Vec2::new(
+        if min.x < max.x {
+            follow.x.clamp(min.x, max.x)
+        } else {
+            room.center().x
+        },
+        if min.y < max.y {
+            follow.y.clamp(min.y, max.y)
+        } else {
+            room.center().y
+        },
+    )
+}
 
-        if let Ok(mut camera_transform) = query_set.p1().get_single_mut() {
-            let camera_x = camera_transform.translation.x;
+/**
+ * the camera eases toward its target at the same pace whatever the frame rate, and jumps straight
+ * there while the room changes behind the curtain - going through a door it centers on the player
+ */
+pub fn follow_player(
+    mut query_set: ParamSet<(PlayerQuery<'_>, CameraQuery<'_>)>,
+    room_state: Res<RoomState>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    time: Res<Time>,
+) {
+    let Ok(player) = query_set
+        .p0()
+        .get_single()
+        .map(|transform| transform.translation.truncate())
+    else {
+        return;
+    };
+    let room = Rect::new(
+        -room_state.wall_distance_from_center,
+        -ROOM_HEIGHT / 2.0,
+        room_state.wall_distance_from_center,
+        ROOM_HEIGHT / 2.0,
+    );
 
-            // If player is near the right edge of the screen
-            if player_x > camera_x + SCREEN_HALF_WIDTH - SCROLL_THRESHOLD {
-                camera_transform.translation.x += CAMERA_MOVE_SPEED;
-            }
-            // If player is near the left edge of the screen
-            if player_x < camera_x - SCREEN_HALF_WIDTH + SCROLL_THRESHOLD {
-                camera_transform.translation.x -= CAMERA_MOVE_SPEED;
-            }
+    if let Ok((mut camera_transform, projection)) = query_set.p1().get_single_mut() {
+        let deadzone = if matches!(
+            state.get(),
+            GameState::TransitioningOut | GameState::RoomChange | GameState::TransitioningIn
+        ) {
+            Vec2::ZERO
+        } else {
+            settings.camera.deadzone.half_size()
+        };
+        let target = camera_target(
+            camera_transform.translation.truncate(),
+            player,
+            deadzone,
+            room,
+            projection.area.half_size(),
+        )
+        .extend(CAMERA_LAYER);
 
-            // Ensure the camera's z value remains consistent
-            camera_transform.translation.z = CAMERA_LAYER; // Define CAMERA_LAYER as needed
+        if *state.get() == GameState::RoomChange {
+            camera_transform.translation = target;
+        } else {
+            camera_transform.translation.smooth_nudge(
+                &target,
+                settings.camera.follow_speed,
+                time.delta_secs(),
+            );
         }
     }
 }
+
+/// zoom is held down to keep zooming, the projection scale is how small the room is drawn
+fn zoom_scale(scale: f32, zoom: f32, camera: &CameraSettings) -> f32 {
+    (scale / zoom).clamp(1.0 / camera.max_zoom, 1.0 / camera.min_zoom)
+}
+
+pub fn zoom_camera(
+    action_query: Query<&ActionState<Action>>,
+    mut zoom_query: Query<&mut CameraZoom>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    let mut direction = 0.0;
+    for action_state in &action_query {
+        if action_state.pressed(&Action::ZoomIn) {
+            direction += 1.0;
+        }
+        if action_state.pressed(&Action::ZoomOut) {
+            direction -= 1.0;
+        }
+    }
+    let zoom = (direction * settings.camera.zoom_speed * time.delta_secs()).exp();
+    for mut camera_zoom in &mut zoom_query {
+        camera_zoom.0 = zoom_scale(camera_zoom.0, zoom, &settings.camera);
+    }
+}
+
+/// the player's zoom, closer still while a transition zooms in on a door
+pub fn scale_camera(
+    fade: Option<Res<FadeEffect>>,
+    mut query: Query<(&CameraZoom, &mut OrthographicProjection)>,
+) {
+    let transition_zoom = fade.map_or(1.0, |fade| fade.zoom);
+    for (camera_zoom, mut projection) in &mut query {
+        projection.scale = camera_zoom.0 / transition_zoom;
+    }
+}
+
+pub fn toggle_fullscreen(
+    action_query: Query<&ActionState<Action>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !action_query
+        .iter()
+        .any(|action_state| action_state.just_pressed(&Action::Fullscreen))
+    {
+        return;
+    }
+    for mut window in &mut window_query {
+        window.mode = match window.mode {
+            WindowMode::Windowed => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+            _ => WindowMode::Windowed,
+        };
+    }
+}
+
+fn ambient_color(health: RoomHealth) -> Color {
+    match health {
+        RoomHealth::Unknown => Color::from(BLUE_300),
+        RoomHealth::Ready => Color::from(GREEN_300),
+        RoomHealth::Pending => Color::from(AMBER_200),
+        RoomHealth::Failing => Color::from(RED_300),
+    }
+}
+
+/**
+* the room takes on the health of the resource it stands for
+*/
+pub fn update_ambient_light(
+    room_state: Res<RoomState>,
+    mut query: Query<&mut AmbientLight2d, With<MainCamera>>,
+) {
+    if !room_state.is_changed() {
+        return;
+    }
+    for mut ambient_light in &mut query {
+        ambient_light.color = ambient_color(room_state.health);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOM: Rect = Rect {
+        min: Vec2::new(-1500.0, -400.0),
+        max: Vec2::new(1500.0, 400.0),
+    };
+    const VIEW: Vec2 = Vec2::new(600.0, 400.0);
+    const DEADZONE: Vec2 = Vec2::new(200.0, 100.0);
+
+    #[test]
+    fn test_camera_keeps_the_player_in_the_deadzone() {
+        let target =
+            |camera: Vec2, player: Vec2| camera_target(camera, player, DEADZONE, ROOM, VIEW);
+
+        // moving about the middle of the screen
+        assert_eq!(target(Vec2::ZERO, Vec2::new(150.0, 0.0)), Vec2::ZERO);
+
+        // running off to the right, then the left
+        assert_eq!(
+            target(Vec2::ZERO, Vec2::new(500.0, 0.0)),
+            Vec2::new(300.0, 0.0)
+        );
+        assert_eq!(
+            target(Vec2::new(300.0, 0.0), Vec2::new(-100.0, 0.0)),
+            Vec2::new(100.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_camera_stays_in_the_room() {
+        let target =
+            |player: Vec2, view: Vec2| camera_target(Vec2::ZERO, player, DEADZONE, ROOM, view);
+
+        // up against the right wall, the floor and ceiling fill the view
+        assert_eq!(target(Vec2::new(1450.0, 0.0), VIEW), Vec2::new(900.0, 0.0));
+
+        // zoomed in, the camera follows the player up but not through the ceiling
+        let zoomed = VIEW / 2.0;
+        assert_eq!(target(Vec2::new(0.0, 380.0), zoomed), Vec2::new(0.0, 200.0));
+
+        // a room narrower than the view stays in the middle of it
+        let wide = Vec2::new(2000.0, 500.0);
+        assert_eq!(target(Vec2::new(1450.0, 380.0), wide), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_zoom_stays_in_bounds() {
+        let camera = Settings::default().camera;
+        assert_eq!(zoom_scale(1.0, 1.0, &camera), 1.0);
+        assert!(zoom_scale(1.0, 1.1, &camera) < 1.0);
+        assert_eq!(zoom_scale(1.0, 100.0, &camera), 1.0 / camera.max_zoom);
+        assert_eq!(zoom_scale(1.0, 0.01, &camera), 1.0 / camera.min_zoom);
+    }
+}
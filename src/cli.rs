@@ -1,4 +1,5 @@
 use clap::Parser;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 pub struct Cli {
@@ -8,13 +9,32 @@ pub struct Cli {
     pub room_generator: Option<RoomGeneratorType>,
     #[arg(long, short, default_value = "60")]
     pub generator_poll_secs: Option<u8>,
+    #[arg(long, default_value = "20")]
+    pub doors_per_section: Option<usize>,
+    #[arg(long, default_value = "alphabetical")]
+    pub door_grouping: Option<DoorGrouping>,
+    #[arg(long, default_value = "app")]
+    pub grouping_label: Option<String>,
+    #[arg(long, default_value = "alphabetical")]
+    pub door_order: Option<DoorOrder>,
+    /// per room kind, ie: `--room-layout Pod=ledges`, `default` for every other room
+    #[arg(long, value_parser = parse_room_layout)]
+    pub room_layout: Vec<(String, RoomLayout)>,
+    /// the key bindings file, read at start and saved by the controls screen
+    #[arg(long, default_value_os_t = default_bindings_path())]
+    pub bindings: PathBuf,
+    /// the movement and physics settings, edits are picked up while the game runs
+    #[arg(long, default_value = "assets/settings.yaml")]
+    pub settings: PathBuf,
 }
 
-#[derive(clap::ValueEnum, Clone, Default)]
+#[derive(clap::ValueEnum, Clone, Copy, Default)]
 pub enum PlayerType {
     #[default]
-    Demo1,
+    Demo1, // the bevy_aseprite_ultra demo character
     Player1,
+    Gabe,
+    Stickman,
 }
 
 #[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Default)]
@@ -26,3 +46,49 @@ pub enum RoomGeneratorType {
     K8sFile,
     K8sLive,
 }
+
+/// how the doors of a room too big for one section are shared out between its sections
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DoorGrouping {
+    #[default]
+    Alphabetical,
+    Owner,
+    Label, // by the value of `--grouping-label`
+}
+
+/// the order doors are laid out along a room
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DoorOrder {
+    #[default]
+    Alphabetical,
+    Kind,
+    Health,  // trouble first
+    Created, // oldest first
+}
+
+/// how the platforms leading to the doors are arranged in a room
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RoomLayout {
+    #[default]
+    Row, // one row just above the floor
+    Tiers,  // rows stacked up to the ceiling
+    Ledges, // a staircase climbing to the ceiling and back down
+}
+
+fn parse_room_layout(value: &str) -> Result<(String, RoomLayout), String> {
+    let (kind, layout) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected KIND=LAYOUT, got '{value}'"))?;
+    let layout = <RoomLayout as clap::ValueEnum>::from_str(layout, true)?;
+    Ok((kind.to_string(), layout))
+}
+
+/// `$XDG_CONFIG_HOME/doors/bindings.yaml`, or under `~/.config` when that isn't set
+fn default_bindings_path() -> PathBuf {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_default()
+        .join("doors")
+        .join("bindings.yaml")
+}
@@ -1,5 +1,6 @@
-pub const WINDOW_WIDTH: f32 = 1200.0;
+pub const WINDOW_WIDTH: f32 = 1200.0; // to start with, the window can be resized or made fullscreen
 pub const WINDOW_HEIGHT: f32 = 800.0;
+pub const ROOM_HEIGHT: f32 = 800.0; // floor to ceiling, the camera fits it to the window's height
 
 pub const PLAYER_LAYER: f32 = 2.0;
 pub const PLATFORM_LAYER: f32 = 0.0;
@@ -7,8 +8,10 @@ pub const TEXT_LAYER: f32 = 0.5;
 pub const DOOR_LAYER: f32 = 0.0;
 pub const LIGHT_LAYER: f32 = 0.0;
 pub const CAMERA_LAYER: f32 = 1000.0;
+pub const TERMINAL_LAYER: f32 = 5.0;
 pub const FADE_OVERLAY_Z: f32 = 10.0;
 
 pub const FADE_OUT_DURATION: f32 = 2.0;
 pub const CURTAIN_DURATION: f32 = 1.5;
 pub const FADE_IN_DURATION: f32 = 2.0;
+pub const DOOR_WALK_DURATION: f32 = 0.8;
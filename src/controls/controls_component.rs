@@ -0,0 +1,536 @@
+use bevy::prelude::*;
+use leafwing_input_manager::{Actionlike, prelude::GamepadControlDirection, prelude::InputMap};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(
+    Actionlike,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Clone,
+    Copy,
+    Debug,
+    Reflect,
+    Serialize,
+    Deserialize,
+)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Enter,
+    Pause,
+    Quit,
+    Inspect, // the terminal showing what's behind the room
+    ScrollUp,
+    ScrollDown,
+    Search, // reserved for finding a room by name, unbound and off the controls screen until then
+    Controls,
+    ZoomIn,
+    ZoomOut,
+    Fullscreen,
+    Skip, // held to hurry through the transition between rooms
+}
+
+impl Action {
+    /// the actions that do something, as listed on the controls screen
+    pub const ALL: [Self; 14] = [
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Jump,
+        Self::Enter,
+        Self::Pause,
+        Self::Quit,
+        Self::Inspect,
+        Self::ScrollUp,
+        Self::ScrollDown,
+        Self::Controls,
+        Self::ZoomIn,
+        Self::ZoomOut,
+        Self::Fullscreen,
+        Self::Skip,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::MoveLeft => "move left",
+            Self::MoveRight => "move right",
+            Self::Jump => "jump",
+            Self::Enter => "go through a door",
+            Self::Pause => "pause",
+            Self::Quit => "quit",
+            Self::Inspect => "inspect the room",
+            Self::ScrollUp => "scroll up",
+            Self::ScrollDown => "scroll down",
+            Self::Search => "search",
+            Self::Controls => "controls",
+            Self::ZoomIn => "zoom in",
+            Self::ZoomOut => "zoom out",
+            Self::Fullscreen => "fullscreen",
+            Self::Skip => "hurry between rooms",
+        }
+    }
+}
+
+/// the keys to start from, the bindings file can change any of them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    #[default]
+    Arrows,
+    Wasd,
+    Vim,
+}
+
+impl Preset {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Arrows => Self::Wasd,
+            Self::Wasd => Self::Vim,
+            Self::Vim => Self::Arrows,
+        }
+    }
+
+    fn keys(self, action: Action) -> Vec<KeyCode> {
+        let (left, right, enter) = match self {
+            Self::Arrows => (KeyCode::ArrowLeft, KeyCode::ArrowRight, KeyCode::ArrowUp),
+            Self::Wasd => (KeyCode::KeyA, KeyCode::KeyD, KeyCode::KeyW),
+            Self::Vim => (KeyCode::KeyH, KeyCode::KeyL, KeyCode::KeyK),
+        };
+        let (scroll_up, scroll_down) = match self {
+            Self::Vim => (KeyCode::KeyU, KeyCode::KeyD),
+            _ => (KeyCode::PageUp, KeyCode::PageDown),
+        };
+        let key = match action {
+            Action::MoveLeft => left,
+            Action::MoveRight => right,
+            Action::Jump => KeyCode::Space,
+            Action::Enter => enter,
+            Action::Pause => KeyCode::Escape,
+            Action::Quit => KeyCode::KeyQ,
+            Action::Inspect => KeyCode::KeyI,
+            Action::ScrollUp => scroll_up,
+            Action::ScrollDown => scroll_down,
+            Action::Search => return Vec::new(),
+            Action::Controls => KeyCode::F1,
+            Action::ZoomIn => KeyCode::Equal,
+            Action::ZoomOut => KeyCode::Minus,
+            Action::Fullscreen => KeyCode::F11,
+            Action::Skip => KeyCode::Tab,
+        };
+        vec![key]
+    }
+
+    /// the same for every preset, quitting and fullscreen are left to the keyboard
+    fn gamepad(action: Action) -> Vec<GamepadButton> {
+        match action {
+            Action::MoveLeft => vec![GamepadButton::DPadLeft],
+            Action::MoveRight => vec![GamepadButton::DPadRight],
+            Action::Jump => vec![GamepadButton::South],
+            Action::Enter => vec![GamepadButton::DPadUp, GamepadButton::North],
+            Action::Pause => vec![GamepadButton::Start],
+            Action::Inspect => vec![GamepadButton::West],
+            Action::ScrollUp => vec![GamepadButton::LeftTrigger],
+            Action::ScrollDown => vec![GamepadButton::RightTrigger],
+            Action::Controls => vec![GamepadButton::Select],
+            Action::Skip => vec![GamepadButton::East],
+            Action::ZoomIn => vec![GamepadButton::RightTrigger2],
+            Action::ZoomOut => vec![GamepadButton::LeftTrigger2],
+            Action::Quit | Action::Search | Action::Fullscreen => Vec::new(),
+        }
+    }
+}
+
+const KEYS: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::Escape,
+    KeyCode::Tab,
+    KeyCode::Backspace,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::Slash,
+    KeyCode::Period,
+    KeyCode::Comma,
+    KeyCode::Semicolon,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+];
+
+/// the keys a bindings file can name
+pub fn bindable(key: KeyCode) -> bool {
+    KEYS.contains(&key)
+}
+
+/// keys by their bevy name, ie: `KeyA`, `ArrowLeft` or `Space` - a single letter will do for letters
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let name = if name.len() == 1 && name.chars().all(|c| c.is_ascii_alphabetic()) {
+        format!("Key{}", name.to_uppercase())
+    } else {
+        name.to_string()
+    };
+    KEYS.iter()
+        .find(|key| format!("{key:?}").eq_ignore_ascii_case(&name))
+        .copied()
+}
+
+/// gamepad buttons by their bevy name, ie: `South` or `DPadLeft`
+pub fn parse_button(name: &str) -> Option<GamepadButton> {
+    GamepadButton::all()
+        .into_iter()
+        .find(|button| format!("{button:?}").eq_ignore_ascii_case(name))
+}
+
+fn names<T: std::fmt::Debug>(buttons: &[T]) -> Vec<String> {
+    buttons.iter().map(|b| format!("{b:?}")).collect()
+}
+
+/// binds the action to `binding` alone, an action that had it already takes the old bindings
+/// in its place so the two swap rather than one key doing two things
+fn swap_in<T: Copy + PartialEq>(
+    bindings: &mut BTreeMap<Action, Vec<T>>,
+    action: Action,
+    binding: T,
+) {
+    let old = bindings.insert(action, vec![binding]).unwrap_or_default();
+    for (other, others) in bindings.iter_mut() {
+        if *other == action || !others.contains(&binding) {
+            continue;
+        }
+        others.retain(|b| *b != binding);
+        for b in &old {
+            if *b != binding && !others.contains(b) {
+                others.push(*b);
+            }
+        }
+    }
+}
+
+/// the bindings file - a preset and the actions bound to something else
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BindingsFile {
+    #[serde(default)]
+    pub preset: Preset,
+    #[serde(default)]
+    pub keys: BTreeMap<Action, Vec<String>>,
+    #[serde(default)]
+    pub gamepad: BTreeMap<Action, Vec<String>>,
+}
+
+/// what every action is bound to right now
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct Bindings {
+    pub preset: Preset,
+    pub keys: BTreeMap<Action, Vec<KeyCode>>,
+    pub gamepad: BTreeMap<Action, Vec<GamepadButton>>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self::preset(Preset::default())
+    }
+}
+
+impl Bindings {
+    pub fn preset(preset: Preset) -> Self {
+        Self {
+            preset,
+            keys: Action::ALL
+                .into_iter()
+                .map(|action| (action, preset.keys(action)))
+                .collect(),
+            gamepad: Action::ALL
+                .into_iter()
+                .map(|action| (action, Preset::gamepad(action)))
+                .collect(),
+        }
+    }
+
+    /// back to what the preset binds the action to
+    pub fn reset(&mut self, action: Action) {
+        self.keys.insert(action, self.preset.keys(action));
+        self.gamepad.insert(action, Preset::gamepad(action));
+    }
+
+    pub fn bind_key(&mut self, action: Action, key: KeyCode) {
+        swap_in(&mut self.keys, action, key);
+    }
+
+    pub fn bind_button(&mut self, action: Action, button: GamepadButton) {
+        swap_in(&mut self.gamepad, action, button);
+    }
+
+    /// anything bound differently from the preset, which switching presets would lose
+    pub fn is_custom(&self) -> bool {
+        *self != Self::preset(self.preset)
+    }
+
+    /// the preset with the file's bindings on top, unknown key or button names are an error
+    pub fn from_file(file: &BindingsFile) -> Result<Self, String> {
+        let mut bindings = Self::preset(file.preset);
+        for (action, names) in &file.keys {
+            let keys = names
+                .iter()
+                .map(|name| parse_key(name).ok_or_else(|| format!("unknown key '{name}'")))
+                .collect::<Result<_, _>>()?;
+            bindings.keys.insert(*action, keys);
+        }
+        for (action, names) in &file.gamepad {
+            let buttons = names
+                .iter()
+                .map(|name| {
+                    parse_button(name).ok_or_else(|| format!("unknown gamepad button '{name}'"))
+                })
+                .collect::<Result<_, _>>()?;
+            bindings.gamepad.insert(*action, buttons);
+        }
+        Ok(bindings)
+    }
+
+    /// every binding is written out, so the file shows what can be changed
+    pub fn to_file(&self) -> BindingsFile {
+        BindingsFile {
+            preset: self.preset,
+            keys: self
+                .keys
+                .iter()
+                .map(|(action, keys)| (*action, names(keys)))
+                .collect(),
+            gamepad: self
+                .gamepad
+                .iter()
+                .map(|(action, buttons)| (*action, names(buttons)))
+                .collect(),
+        }
+    }
+
+    /// the left stick always moves the player as well
+    pub fn input_map(&self) -> InputMap<Action> {
+        let mut input_map = InputMap::default();
+        for (action, keys) in &self.keys {
+            for key in keys {
+                input_map.insert(*action, *key);
+            }
+        }
+        for (action, buttons) in &self.gamepad {
+            for button in buttons {
+                input_map.insert(*action, *button);
+            }
+        }
+        input_map
+            .insert(Action::MoveLeft, GamepadControlDirection::LEFT_LEFT)
+            .insert(Action::MoveRight, GamepadControlDirection::LEFT_RIGHT);
+        input_map
+    }
+
+    /// what the action is bound to, for the controls screen
+    pub fn describe(&self, action: Action) -> String {
+        let listed = |buttons: Vec<String>| {
+            if buttons.is_empty() {
+                "-".to_string()
+            } else {
+                buttons.join(", ")
+            }
+        };
+        format!(
+            "{}   |   {}",
+            listed(self.keys.get(&action).map(|k| names(k)).unwrap_or_default()),
+            listed(
+                self.gamepad
+                    .get(&action)
+                    .map(|b| names(b))
+                    .unwrap_or_default()
+            )
+        )
+    }
+}
+
+/// where the player is on the controls screen
+#[derive(Resource, Default)]
+pub struct ControlsScreen {
+    pub selected: usize,
+    pub waiting: bool, // for a key or button to bind to the selected action
+    pub confirming_preset: bool, // tab again to switch presets and drop the custom bindings
+}
+
+#[derive(Component)]
+pub struct ControlsPanel;
+
+#[derive(Component)]
+pub struct ControlsText;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_bind_every_action() {
+        for preset in [Preset::Arrows, Preset::Wasd, Preset::Vim] {
+            let bindings = Bindings::preset(preset);
+            for action in Action::ALL {
+                assert!(!bindings.keys[&action].is_empty(), "{preset:?} {action:?}");
+            }
+        }
+        assert_eq!(
+            Bindings::preset(Preset::Wasd).keys[&Action::MoveLeft],
+            vec![KeyCode::KeyA]
+        );
+        assert_eq!(
+            Bindings::preset(Preset::Vim).keys[&Action::Enter],
+            vec![KeyCode::KeyK]
+        );
+    }
+
+    #[test]
+    fn test_bind_key_swaps_with_the_action_using_it() {
+        let mut bindings = Bindings::preset(Preset::Arrows);
+        assert!(!bindings.is_custom());
+
+        bindings.bind_key(Action::Jump, KeyCode::ArrowUp);
+
+        assert_eq!(bindings.keys[&Action::Jump], vec![KeyCode::ArrowUp]);
+        assert_eq!(bindings.keys[&Action::Enter], vec![KeyCode::Space]);
+        assert!(bindings.is_custom());
+
+        bindings.bind_button(Action::Jump, GamepadButton::West);
+
+        assert_eq!(bindings.gamepad[&Action::Jump], vec![GamepadButton::West]);
+        assert_eq!(
+            bindings.gamepad[&Action::Inspect],
+            vec![GamepadButton::South]
+        );
+    }
+
+    #[test]
+    fn test_presets_bind_nothing_twice() {
+        for preset in [Preset::Arrows, Preset::Wasd, Preset::Vim] {
+            let bindings = Bindings::preset(preset);
+            let keys: Vec<KeyCode> = bindings.keys.values().flatten().copied().collect();
+            let buttons: Vec<GamepadButton> =
+                bindings.gamepad.values().flatten().copied().collect();
+            for key in &keys {
+                assert_eq!(
+                    keys.iter().filter(|k| *k == key).count(),
+                    1,
+                    "{preset:?} {key:?}"
+                );
+            }
+            for button in &buttons {
+                assert_eq!(
+                    buttons.iter().filter(|b| *b == button).count(),
+                    1,
+                    "{button:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_key_and_button() {
+        assert_eq!(parse_key("ArrowLeft"), Some(KeyCode::ArrowLeft));
+        assert_eq!(parse_key("space"), Some(KeyCode::Space));
+        assert_eq!(parse_key("j"), Some(KeyCode::KeyJ));
+        assert_eq!(parse_key("Banana"), None);
+        assert_eq!(parse_button("DPadLeft"), Some(GamepadButton::DPadLeft));
+        assert_eq!(parse_button("south"), Some(GamepadButton::South));
+    }
+
+    #[test]
+    fn test_bindings_from_file() {
+        let file: BindingsFile = serde_yaml::from_str(
+            "preset: vim\nkeys:\n  Jump: [j, Space]\ngamepad:\n  Quit: [Mode]\n",
+        )
+        .expect("bindings file expected");
+        let bindings = Bindings::from_file(&file).expect("bindings expected");
+
+        assert_eq!(bindings.preset, Preset::Vim);
+        assert_eq!(
+            bindings.keys[&Action::Jump],
+            vec![KeyCode::KeyJ, KeyCode::Space]
+        );
+        assert_eq!(bindings.keys[&Action::MoveLeft], vec![KeyCode::KeyH]);
+        assert_eq!(bindings.gamepad[&Action::Quit], vec![GamepadButton::Mode]);
+
+        let unknown = BindingsFile {
+            keys: BTreeMap::from([(Action::Jump, vec!["Banana".to_string()])]),
+            ..Default::default()
+        };
+        assert!(Bindings::from_file(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_bindings_file_round_trip() {
+        let mut bindings = Bindings::preset(Preset::Wasd);
+        bindings.keys.insert(Action::Jump, vec![KeyCode::KeyJ]);
+        let yaml = serde_yaml::to_string(&bindings.to_file()).expect("yaml expected");
+        let file: BindingsFile = serde_yaml::from_str(&yaml).expect("bindings file expected");
+
+        assert_eq!(Bindings::from_file(&file), Ok(bindings.clone()));
+
+        bindings.reset(Action::Jump);
+        assert_eq!(bindings, Bindings::preset(Preset::Wasd));
+    }
+}
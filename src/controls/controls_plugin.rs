@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use super::controls_component::ControlsScreen;
+use super::controls_systems::{
+    hide_controls, load_bindings, open_controls, rebind_controls, show_controls, update_controls,
+};
+use crate::{
+    schedule::InGameSet,
+    state::GameState::{Controls, InGame},
+};
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ControlsScreen>()
+            .add_systems(Startup, load_bindings)
+            .add_systems(
+                Update,
+                open_controls
+                    .in_set(InGameSet::Update)
+                    .run_if(in_state(InGame)),
+            )
+            .add_systems(OnEnter(Controls), show_controls)
+            .add_systems(
+                Update,
+                (rebind_controls, update_controls)
+                    .chain()
+                    .in_set(InGameSet::Update)
+                    .run_if(in_state(Controls)),
+            )
+            .add_systems(OnExit(Controls), hide_controls);
+    }
+}
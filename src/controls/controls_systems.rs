@@ -0,0 +1,224 @@
+use bevy::color::palettes::tailwind::{AMBER_400, SLATE_200};
+use bevy::prelude::*;
+use clap::Parser;
+use leafwing_input_manager::prelude::{ActionState, InputMap};
+
+use super::controls_component::{
+    Action, Bindings, BindingsFile, ControlsPanel, ControlsScreen, ControlsText, bindable,
+};
+use crate::cli::Cli;
+use crate::state::GameState;
+
+static CONTROLS_BACKGROUND_COLOR: Color = Color::srgba(0.02, 0.02, 0.05, 0.9);
+const CONTROLS_FONT_SIZE: f32 = 20.0;
+const CONTROLS_PADDING: f32 = 40.0;
+
+/**
+ * the bindings file on top of its preset - a missing file is the arrow keys, a broken one is
+ * reported and ignored
+ */
+pub fn load_bindings(mut commands: Commands) {
+    let path = Cli::parse().bindings;
+    let bindings = std::fs::read_to_string(&path).map_or_else(
+        |_| Bindings::default(),
+        |yaml| {
+            serde_yaml::from_str::<BindingsFile>(&yaml)
+                .map_err(|e| e.to_string())
+                .and_then(|file| Bindings::from_file(&file))
+                .unwrap_or_else(|e| {
+                    warn!("ignoring key bindings in {}: {e}", path.display());
+                    Bindings::default()
+                })
+        },
+    );
+    commands.insert_resource(bindings);
+}
+
+fn save_bindings(bindings: &Bindings) {
+    let path = Cli::parse().bindings;
+    let saved = serde_yaml::to_string(&bindings.to_file())
+        .map_err(|e| e.to_string())
+        .and_then(|yaml| {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            std::fs::write(&path, yaml).map_err(|e| e.to_string())
+        });
+    if let Err(e) = saved {
+        warn!("could not save key bindings to {}: {e}", path.display());
+    }
+}
+
+pub fn open_controls(
+    action_query: Query<&ActionState<Action>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if action_query
+        .iter()
+        .any(|action_state| action_state.just_pressed(&Action::Controls))
+    {
+        next_state.set(GameState::Controls);
+    }
+}
+
+/**
+ * up and down pick an action, enter waits for the key or gamepad button to bind it to, backspace
+ * puts it back to the preset's and tab moves on to the next preset - asking first when that would
+ * drop bindings of the player's own
+ */
+pub fn rebind_controls(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    action_query: Query<&ActionState<Action>>,
+    mut screen: ResMut<ControlsScreen>,
+    mut bindings: ResMut<Bindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let action = Action::ALL[screen.selected];
+    if screen.waiting {
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            screen.waiting = false;
+        } else if let Some(key) = keyboard_input.get_just_pressed().find(|k| bindable(**k)) {
+            bindings.bind_key(action, *key);
+            screen.waiting = false;
+        } else if let Some(button) = gamepads.iter().find_map(|g| g.get_just_pressed().next()) {
+            bindings.bind_button(action, *button);
+            screen.waiting = false;
+        }
+        return;
+    }
+    if screen.confirming_preset
+        && keyboard_input
+            .get_just_pressed()
+            .any(|key| *key != KeyCode::Tab)
+    {
+        screen.confirming_preset = false;
+    }
+    if keyboard_input.just_pressed(KeyCode::Escape)
+        || action_query
+            .iter()
+            .any(|action_state| action_state.just_pressed(&Action::Controls))
+    {
+        next_state.set(GameState::InGame);
+    } else if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        screen.selected = screen
+            .selected
+            .checked_sub(1)
+            .unwrap_or(Action::ALL.len() - 1);
+    } else if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        screen.selected = (screen.selected + 1) % Action::ALL.len();
+    } else if keyboard_input.just_pressed(KeyCode::Enter) {
+        screen.waiting = true;
+    } else if keyboard_input.just_pressed(KeyCode::Backspace) {
+        bindings.reset(action);
+    } else if keyboard_input.just_pressed(KeyCode::Tab) {
+        if bindings.is_custom() && !screen.confirming_preset {
+            screen.confirming_preset = true;
+        } else {
+            screen.confirming_preset = false;
+            *bindings = Bindings::preset(bindings.preset.next());
+        }
+    }
+}
+
+fn controls_text(screen: &ControlsScreen, bindings: &Bindings) -> String {
+    let mut lines = vec![
+        format!("CONTROLS - {:?} preset", bindings.preset),
+        String::new(),
+    ];
+    for (i, action) in Action::ALL.into_iter().enumerate() {
+        let marker = if i != screen.selected {
+            "  "
+        } else if screen.waiting {
+            "? "
+        } else {
+            "> "
+        };
+        lines.push(format!(
+            "{marker}{:<20}{}",
+            action.label(),
+            bindings.describe(action)
+        ));
+    }
+    lines.push(String::new());
+    lines.push(if screen.waiting {
+        "press a key or gamepad button - esc to cancel".to_string()
+    } else if screen.confirming_preset {
+        "tab again to switch presets and lose your own bindings - any other key keeps them"
+            .to_string()
+    } else {
+        "up/down select   enter rebind   backspace reset   tab next preset   esc done".to_string()
+    });
+    lines.join("\n")
+}
+
+pub fn show_controls(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    screen: Res<ControlsScreen>,
+    bindings: Res<Bindings>,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(CONTROLS_PADDING)),
+                ..default()
+            },
+            BackgroundColor(CONTROLS_BACKGROUND_COLOR),
+            ControlsPanel,
+        ))
+        .with_children(|builder| {
+            builder.spawn((
+                Text::new(controls_text(&screen, &bindings)),
+                TextFont {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: CONTROLS_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(SLATE_200.into()),
+                ControlsText,
+            ));
+        });
+}
+
+pub fn update_controls(
+    screen: Res<ControlsScreen>,
+    bindings: Res<Bindings>,
+    mut query: Query<(&mut Text, &mut TextColor), With<ControlsText>>,
+) {
+    if !screen.is_changed() && !bindings.is_changed() {
+        return;
+    }
+    for (mut text, mut color) in &mut query {
+        text.0 = controls_text(&screen, &bindings);
+        color.0 = if screen.waiting || screen.confirming_preset {
+            AMBER_400.into()
+        } else {
+            SLATE_200.into()
+        };
+    }
+}
+
+/**
+ * the new bindings take effect as the screen closes and are saved for next time
+ */
+pub fn hide_controls(
+    mut commands: Commands,
+    bindings: Res<Bindings>,
+    mut screen: ResMut<ControlsScreen>,
+    panel_query: Query<Entity, With<ControlsPanel>>,
+    mut input_query: Query<&mut InputMap<Action>>,
+) {
+    for entity in panel_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    screen.waiting = false;
+    screen.confirming_preset = false;
+    for mut input_map in &mut input_query {
+        *input_map = bindings.input_map();
+    }
+    save_bindings(&bindings);
+}
@@ -0,0 +1,6 @@
+pub mod controls_component;
+pub mod controls_plugin;
+pub mod controls_systems;
+
+pub use controls_component::{Action, Bindings};
+pub use controls_plugin::ControlsPlugin;
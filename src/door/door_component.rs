@@ -1,8 +1,7 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Component, Resource};
 
 pub const PLATFORM_WIDTH: f32 = 200.0;
 pub const PLATFORM_HEIGHT: f32 = 20.0;
-pub const BOUNCE_EFFECT: f32 = 0.1;
 
 #[derive(Component)]
 pub struct Platform {}
@@ -11,3 +10,10 @@ pub struct Platform {}
 pub struct Door {
     pub room_id: String,
 }
+
+/// the door the player is walking through, into the old room or out into the new one
+#[derive(Resource, Default)]
+pub struct DoorWalk {
+    pub room_id: Option<String>,
+    pub elapsed: f32,
+}
@@ -1,19 +1,27 @@
 use bevy::prelude::*;
 
-use crate::{room::room_systems::update_room, schedule::InGameSet, state::GameState::RoomChange};
+use crate::{
+    room::room_systems::update_room,
+    schedule::InGameSet,
+    state::GameState::{EnteringDoor, LeavingDoor, RoomChange},
+};
 
-use super::door_systems::{despawn_existing_platforms, spawn_platforms};
+use super::door_component::DoorWalk;
+use super::door_systems::{close_door, despawn_existing_platforms, open_door, spawn_platforms};
 
 pub struct DoorPlugin;
 
 impl Plugin for DoorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(RoomChange),
-            (despawn_existing_platforms, spawn_platforms)
-                .in_set(InGameSet::Render)
-                .chain()
-                .after(update_room),
-        );
+        app.init_resource::<DoorWalk>()
+            .add_systems(OnEnter(EnteringDoor), open_door)
+            .add_systems(OnExit(LeavingDoor), close_door)
+            .add_systems(
+                OnEnter(RoomChange),
+                (despawn_existing_platforms, spawn_platforms)
+                    .in_set(InGameSet::Render)
+                    .chain()
+                    .after(update_room),
+            );
     }
 }
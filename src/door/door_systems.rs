@@ -1,22 +1,30 @@
-use super::door_component::{BOUNCE_EFFECT, Door, PLATFORM_HEIGHT, PLATFORM_WIDTH, Platform};
+use super::door_component::{Door, DoorWalk, PLATFORM_HEIGHT, PLATFORM_WIDTH, Platform};
 use crate::{
     constants::{DOOR_LAYER, LIGHT_LAYER, PLATFORM_LAYER, TEXT_LAYER},
+    floorplan::RoomHealth,
     room::room_component::{DoorState, RoomState},
+    settings::{Settings, settings_component::SurfaceSettings},
 };
 use avian2d::{parry::shape::SharedShape, prelude::*};
 use bevy::{
-    color::palettes::{css::GREY, tailwind::BLUE_600},
+    color::palettes::{
+        css::GREY,
+        tailwind::{AMBER_500, BLUE_600, EMERALD_400, GREEN_600, RED_600},
+    },
     prelude::*,
     sprite::Anchor,
     text::TextBounds,
 };
+use bevy_aseprite_ultra::prelude::*;
 use bevy_lit::prelude::{LightOccluder2d, PointLight2d};
+use std::collections::BTreeMap;
 
 #[allow(clippy::type_complexity)]
 pub fn spawn_platforms(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     room_state: Res<RoomState>,
+    settings: Res<Settings>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
     if !room_state.is_changed() {
@@ -33,22 +41,71 @@ pub fn spawn_platforms(
 
     //despawn_existing_platforms(&mut commands, query);
     let font = asset_server.load("fonts/FiraSans-Bold.ttf");
-    let text_font = create_text_font(font.clone());
-    let sign_font = create_sign_font(font);
+    let assets = PlatformAssets {
+        text_font: create_text_font(font.clone()),
+        sign_font: create_sign_font(font),
+        door_aseprite: asset_server.load("door.aseprite"),
+        surface: settings.platforms.surface(),
+    };
 
     for door_state in room_state.clone().doors {
         spawn_platform(
             &mut commands,
             room_state.clone(),
             door_state,
-            &text_font,
-            &sign_font,
+            &assets,
             &mut meshes,
             room_name.clone(),
         );
     }
 }
 
+/**
+ * the door the player is walking through swings open, and stays open until they're through
+ */
+pub fn open_door(
+    door_walk: Res<DoorWalk>,
+    mut door_query: Query<(&Door, &mut AseSpriteAnimation)>,
+) {
+    for (door, mut ase_sprite_animation) in &mut door_query {
+        if door_walk.room_id.as_ref() == Some(&door.room_id) {
+            ase_sprite_animation
+                .animation
+                .play("opening", AnimationRepeat::Count(0));
+            ase_sprite_animation
+                .animation
+                .then("open", AnimationRepeat::Loop);
+        }
+    }
+}
+
+/**
+* the door the player came out of swings shut behind them
+*/
+pub fn close_door(
+    room_state: Res<RoomState>,
+    mut door_query: Query<(&Door, &mut AseSpriteAnimation)>,
+) {
+    for (door, mut ase_sprite_animation) in &mut door_query {
+        if room_state.previous_room_id.as_ref() == Some(&door.room_id) {
+            ase_sprite_animation
+                .animation
+                .play("closing", AnimationRepeat::Count(0));
+            ase_sprite_animation
+                .animation
+                .then("closed", AnimationRepeat::Loop);
+        }
+    }
+}
+
+/// what every platform in a room is made with
+struct PlatformAssets {
+    text_font: TextFont,
+    sign_font: TextFont,
+    door_aseprite: Handle<Aseprite>,
+    surface: SurfaceSettings,
+}
+
 pub fn despawn_existing_platforms(mut commands: Commands, query: Query<Entity, With<Platform>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
@@ -71,15 +128,107 @@ fn create_sign_font(font: Handle<Font>) -> TextFont {
     }
 }
 
+/**
+* doors glow with the health of the room behind them so trouble shows from down the hallway
+*/
+fn door_light_color(health: RoomHealth) -> Color {
+    match health {
+        RoomHealth::Unknown => Color::from(BLUE_600),
+        RoomHealth::Ready => Color::from(GREEN_600),
+        RoomHealth::Pending => Color::from(AMBER_500),
+        RoomHealth::Failing => Color::from(RED_600),
+    }
+}
+
+/// exits are painted and signed like the real thing, so the way out is never in doubt
+fn door_color(is_exit: bool) -> Color {
+    if is_exit {
+        Color::from(EMERALD_400)
+    } else {
+        Color::WHITE
+    }
+}
+
+fn create_exit_sign_component(sign_font: &TextFont) -> impl Bundle {
+    (
+        Text2d::new("EXIT"),
+        TextColor(Color::from(EMERALD_400)),
+        sign_font.clone(),
+        Anchor::BottomCenter,
+        Transform::from_translation(Vec3::new(
+            0.0,
+            PLATFORM_HEIGHT.mul_add(0.5, PLATFORM_WIDTH / 2.0) + 5.0,
+            TEXT_LAYER,
+        )),
+    )
+}
+
+fn create_door_component(
+    room_id: String,
+    is_exit: bool,
+    open: bool,
+    door_aseprite: &Handle<Aseprite>,
+) -> impl Bundle {
+    (
+        Door { room_id },
+        Transform::from_xyz(
+            0.0,
+            PLATFORM_HEIGHT / 2.0 + PLATFORM_WIDTH / 4.0,
+            DOOR_LAYER,
+        ),
+        Sprite {
+            color: door_color(is_exit),
+            custom_size: Some(Vec2::new(PLATFORM_WIDTH / 4.0, PLATFORM_WIDTH / 2.0)),
+            ..default()
+        },
+        AseSpriteAnimation {
+            animation: Animation::tag(if open { "open" } else { "closed" }),
+            aseprite: door_aseprite.clone(),
+        },
+    )
+}
+
+fn platform_color(health: RoomHealth) -> Color {
+    let grey = Color::srgb(0.5, 0.5, 0.5);
+    match health {
+        RoomHealth::Unknown => grey,
+        _ => grey.mix(&door_light_color(health), 0.4),
+    }
+}
+
+fn create_attributes_text_component(
+    attributes: &BTreeMap<String, String>,
+    text_font: &TextFont,
+) -> impl Bundle {
+    let lines: Vec<String> = attributes
+        .iter()
+        .map(|(key, value)| format!("{key}: {value}"))
+        .collect();
+    (
+        Text2d::new(lines.join("\n")),
+        TextColor(bevy::prelude::Color::Srgba(GREY)),
+        text_font.clone(),
+        Anchor::TopCenter,
+        TextLayout::new(JustifyText::Left, LineBreak::WordBoundary),
+        TextBounds::from(Vec2::new(PLATFORM_WIDTH * 2.0, PLATFORM_HEIGHT * 10.0)),
+        Transform::from_translation(Vec3::new(0.0, -75.0, TEXT_LAYER)),
+    )
+}
+
 fn spawn_platform(
     commands: &mut Commands,
     room_state: RoomState,
     door_state: DoorState,
-    text_font: &TextFont,
-    sign_font: &TextFont,
+    assets: &PlatformAssets,
     meshes: &mut ResMut<Assets<Mesh>>,
     room_name: String,
 ) {
+    let PlatformAssets {
+        text_font,
+        sign_font,
+        door_aseprite,
+        surface,
+    } = assets;
     debug!("Spawning platform for room: {room_name}");
     let platform_shape = meshes.add(Rectangle::new(PLATFORM_WIDTH, PLATFORM_HEIGHT));
 
@@ -91,19 +240,12 @@ fn spawn_platform(
             PLATFORM_WIDTH / 2.0,
             PLATFORM_HEIGHT / 2.0,
         )),
-        Friction {
-            dynamic_coefficient: 0.6,
-            static_coefficient: 0.8,
-            combine_rule: CoefficientCombine::Average,
-        },
-        Restitution {
-            coefficient: BOUNCE_EFFECT,
-            combine_rule: CoefficientCombine::Max,
-        },
+        surface.friction.friction(),
+        surface.restitution(),
         Platform {},
         Transform::from_xyz(door_state.position.x, door_state.position.y, PLATFORM_LAYER),
         Sprite {
-            color: Color::srgb(0.5, 0.5, 0.5),
+            color: platform_color(door_state.health),
             custom_size: Some(Vec2::new(PLATFORM_WIDTH, PLATFORM_HEIGHT)),
             ..default()
         },
@@ -118,6 +260,9 @@ fn spawn_platform(
         Transform::from_translation(Vec3::new(0.0, 0.0, TEXT_LAYER)), // Set a high z value
     );
 
+    // the player just came through this door, so it is still open
+    let came_through = room_state.previous_room_id.as_ref() == Some(&door_state.room_id);
+
     let exit_text_component = room_state.previous_room_id.and_then(|previous_room_id| {
         if door_state.room_id == previous_room_id {
             Some((
@@ -134,20 +279,20 @@ fn spawn_platform(
         }
     });
 
-    let door_component = (
-        Door {
-            room_id: door_state.room_id,
-        },
-        Transform::from_xyz(
-            0.0,
-            PLATFORM_HEIGHT / 2.0 + PLATFORM_WIDTH / 4.0,
-            DOOR_LAYER,
-        ),
-        Sprite {
-            color: Color::srgb(0.3, 0.3, 0.3),
-            custom_size: Some(Vec2::new(PLATFORM_WIDTH / 4.0, PLATFORM_WIDTH / 2.0)),
-            ..default()
-        },
+    // the details of the room are posted under the sign by the way back out
+    let attributes_text_component = (exit_text_component.is_some()
+        && !room_state.attributes.is_empty())
+    .then(|| create_attributes_text_component(&room_state.attributes, text_font));
+
+    let exit_sign_component = door_state
+        .is_exit
+        .then(|| create_exit_sign_component(sign_font));
+
+    let door_component = create_door_component(
+        door_state.room_id,
+        door_state.is_exit,
+        came_through,
+        door_aseprite,
     );
 
     let light_component = (
@@ -155,7 +300,7 @@ fn spawn_platform(
             intensity: 1.5,
             radius: 600.0,
             falloff: 2.0,
-            color: Color::from(BLUE_600),
+            color: door_light_color(door_state.health),
             ..default()
         },
         Transform::from_xyz(
@@ -172,5 +317,11 @@ fn spawn_platform(
         if let Some(exit_text_component) = exit_text_component {
             builder.spawn(exit_text_component);
         }
+        if let Some(attributes_text_component) = attributes_text_component {
+            builder.spawn(attributes_text_component);
+        }
+        if let Some(exit_sign_component) = exit_sign_component {
+            builder.spawn(exit_sign_component);
+        }
     });
 }
@@ -3,5 +3,6 @@ pub mod door_plugin;
 pub mod door_systems;
 
 pub use door_component::Door;
+pub use door_component::DoorWalk;
 pub use door_component::Platform;
 pub use door_plugin::DoorPlugin;
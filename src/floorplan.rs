@@ -1,18 +1,44 @@
 #![allow(dead_code)]
 use bevy::prelude::{Event, States};
-use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::graph::{DiGraph, EdgeIndex, NodeIndex};
 use petgraph::visit::EdgeRef;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 #[derive(Event)]
 pub struct FloorPlanEvent {
     pub floorplan: FloorPlan,
 }
 
+/**
+* how the real-world thing behind a room is doing, ie: a crash looping pod is `Failing`
+*/
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum RoomHealth {
+    #[default]
+    Unknown,
+    Ready,
+    Pending,
+    Failing,
+}
+
+/// the real-world resource a room stands for, so the game can ask the outside world about it
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct RoomResource {
+    pub kind: String,
+    pub namespace: Option<String>, // none for cluster scoped resources
+    pub name: String,
+    pub owner: Option<String>, // ie: "ReplicaSet web-5d8f"
+    pub labels: BTreeMap<String, String>,
+    pub created: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, States)]
 pub struct RoomData {
     pub id: String,
     pub name: String,
+    pub attributes: BTreeMap<String, String>, // details from the real world, ie: a node's capacity
+    pub health: RoomHealth,
+    pub resource: Option<RoomResource>,
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, States)]
@@ -31,6 +57,9 @@ pub enum FloorPlanError {
 
 pub type FloorPlanResult<T> = Result<T, FloorPlanError>;
 
+// a section of a partitioned room - its group and its doors with the names of the rooms behind them
+type Section = (Option<String>, Vec<(String, EdgeIndex)>);
+
 #[derive(Debug, Clone, Default, States)]
 pub struct FloorPlan {
     graph: DiGraph<RoomData, DoorData>,
@@ -154,6 +183,108 @@ impl FloorPlan {
         )
     }
 
+    /**
+     * splits every room with more than `doors_per_section` doors into a chain of sections joined by
+     * next and previous doors.  doors are sorted by `group_key` and then by the name of the room
+     * they lead to, and each group gets its own sections.  the room itself stays the first section
+     * and keeps its exits, and the rooms behind moved doors get their way back pointed at the
+     * section they are now in.
+     */
+    pub fn partition_large_rooms(
+        &mut self,
+        doors_per_section: usize,
+        group_key: impl Fn(&RoomData) -> Option<String>,
+    ) -> FloorPlanResult<()> {
+        let doors_per_section = doors_per_section.max(1);
+        let room_indexes: Vec<NodeIndex> = self.graph.node_indices().collect();
+        for room_index in room_indexes {
+            // the exit stays in the first section, so only the doors shared out are counted
+            let mut doors: Vec<(Option<String>, String, EdgeIndex)> = self
+                .graph
+                .edges(room_index)
+                .filter(|edge| !edge.weight().is_exit)
+                .map(|edge| {
+                    let target = &self.graph[edge.target()];
+                    (group_key(target), target.name.clone(), edge.id())
+                })
+                .collect();
+            if doors.len() <= doors_per_section {
+                continue;
+            }
+            doors.sort();
+
+            let mut sections: Vec<Section> = Vec::new();
+            for (group, name, edge) in doors {
+                match sections.last_mut() {
+                    Some((section_group, section_doors))
+                        if *section_group == group && section_doors.len() < doors_per_section =>
+                    {
+                        section_doors.push((name, edge));
+                    }
+                    _ => sections.push((group, vec![(name, edge)])),
+                }
+            }
+            self.add_sections(room_index, &sections)?;
+        }
+        Ok(())
+    }
+
+    fn add_sections(&mut self, room_index: NodeIndex, sections: &[Section]) -> FloorPlanResult<()> {
+        let room = self.get_room(room_index)?.clone();
+        let mut moved: HashSet<EdgeIndex> = HashSet::new();
+        let mut new_doors: Vec<(NodeIndex, NodeIndex, DoorData)> = Vec::new();
+        let mut previous_index = room_index;
+        for (number, (group, section_doors)) in sections.iter().enumerate().skip(1) {
+            let first = section_doors.first().map_or("", |(name, _)| name.as_str());
+            let last = section_doors.last().map_or("", |(name, _)| name.as_str());
+            let label = group
+                .clone()
+                .unwrap_or_else(|| format!("{first} .. {last}"));
+            let section_index = self.add_room(RoomData {
+                id: format!("{}-section-{}", room.id, number + 1),
+                name: format!("{} ({label})", room.name),
+                ..Default::default()
+            });
+            for (_, edge) in section_doors {
+                let (_, target_index) = self
+                    .graph
+                    .edge_endpoints(*edge)
+                    .ok_or_else(|| FloorPlanError::DoorNotFound(edge.index().to_string()))?;
+                moved.insert(*edge);
+                new_doors.push((section_index, target_index, self.graph[*edge].clone()));
+                // the way back from the room behind the door now leads to this section
+                for back_edge in self.graph.edges_connecting(target_index, room_index) {
+                    moved.insert(back_edge.id());
+                    new_doors.push((target_index, section_index, back_edge.weight().clone()));
+                }
+            }
+            new_doors.push((
+                previous_index,
+                section_index,
+                DoorData {
+                    id: format!("{}-section-{}-next", room.id, number + 1),
+                    name: format!("Next section ({label})"),
+                    is_exit: false,
+                },
+            ));
+            new_doors.push((
+                section_index,
+                previous_index,
+                DoorData {
+                    id: format!("{}-section-{}-previous", room.id, number + 1),
+                    name: "Previous section".to_string(),
+                    is_exit: true,
+                },
+            ));
+            previous_index = section_index;
+        }
+        self.graph.retain_edges(|_, edge| !moved.contains(&edge));
+        for (from, to, door) in new_doors {
+            self.add_door(from, to, door);
+        }
+        Ok(())
+    }
+
     pub fn get_doors_and_connected_rooms(
         &self,
         room_id: &str,
@@ -185,10 +316,12 @@ mod tests {
         let room1 = RoomData {
             id: "1".to_string(),
             name: "RoomData 1".to_string(),
+            ..Default::default()
         };
         let room2 = RoomData {
             id: "2".to_string(),
             name: "RoomData 2".to_string(),
+            ..Default::default()
         };
 
         let room1_index = floor_plan.add_room(room1);
@@ -218,14 +351,17 @@ mod tests {
         let room1 = RoomData {
             id: "1".to_string(),
             name: "RoomData 1".to_string(),
+            ..Default::default()
         };
         let room2 = RoomData {
             id: "2".to_string(),
             name: "RoomData 2".to_string(),
+            ..Default::default()
         };
         let room3 = RoomData {
             id: "3".to_string(),
             name: "RoomData 3".to_string(),
+            ..Default::default()
         };
 
         let room1_index = floor_plan.add_room(room1);
@@ -267,14 +403,17 @@ mod tests {
         let room1 = RoomData {
             id: "1".to_string(),
             name: "RoomData 1".to_string(),
+            ..Default::default()
         };
         let room2 = RoomData {
             id: "2".to_string(),
             name: "RoomData 2".to_string(),
+            ..Default::default()
         };
         let room3 = RoomData {
             id: "3".to_string(),
             name: "RoomData 3".to_string(),
+            ..Default::default()
         };
 
         floor_plan.add_room(room1.clone());
@@ -320,10 +459,12 @@ mod tests {
         let room1 = RoomData {
             id: "1".to_string(),
             name: "RoomData 1".to_string(),
+            ..Default::default()
         };
         let room2 = RoomData {
             id: "2".to_string(),
             name: "RoomData 2".to_string(),
+            ..Default::default()
         };
 
         floor_plan.add_room(room1.clone());
@@ -334,4 +475,125 @@ mod tests {
         floor_plan.set_start_room(&room2.id).unwrap();
         assert_eq!(floor_plan.get_start_room().unwrap().name, "RoomData 2");
     }
+
+    /// a hallway with a way out to a lobby and `count` rooms off it, each with a door back
+    fn hallway_floor_plan(count: usize) -> FloorPlan {
+        let mut floor_plan = FloorPlan::new();
+        let hallway = floor_plan.add_room(RoomData {
+            id: "hallway".to_string(),
+            name: "Hallway".to_string(),
+            ..Default::default()
+        });
+        let lobby = floor_plan.add_room(RoomData {
+            id: "lobby".to_string(),
+            name: "Lobby".to_string(),
+            ..Default::default()
+        });
+        let exit = DoorData {
+            id: "exit".to_string(),
+            name: "Door to Lobby".to_string(),
+            is_exit: true,
+        };
+        floor_plan.add_door(hallway, lobby, exit);
+        for i in 0..count {
+            let room = floor_plan.add_room(RoomData {
+                id: format!("room-{i:02}"),
+                name: format!("Room {i:02}"),
+                attributes: BTreeMap::from([("team".to_string(), (i % 2).to_string())]),
+                ..Default::default()
+            });
+            let door_in = DoorData {
+                id: format!("in-{i}"),
+                name: format!("Door to Room {i:02}"),
+                is_exit: false,
+            };
+            let door_out = DoorData {
+                id: format!("out-{i}"),
+                name: "Door to Hallway".to_string(),
+                is_exit: true,
+            };
+            floor_plan.add_door(hallway, room, door_in);
+            floor_plan.add_door(room, hallway, door_out);
+        }
+        floor_plan
+    }
+
+    fn door_targets(floor_plan: &FloorPlan, room_id: &str) -> Vec<String> {
+        let mut targets: Vec<String> = floor_plan
+            .get_doors_and_connected_rooms(room_id)
+            .unwrap()
+            .into_iter()
+            .map(|(_, room)| room.id.clone())
+            .collect();
+        targets.sort();
+        targets
+    }
+
+    #[test]
+    fn test_partition_large_rooms() {
+        let mut floor_plan = hallway_floor_plan(25);
+
+        floor_plan.partition_large_rooms(10, |_| None).unwrap();
+
+        let first = door_targets(&floor_plan, "hallway");
+        assert_eq!(first.len(), 12); // ten rooms, the lobby and the next section
+        assert!(first.contains(&"lobby".to_string()));
+        assert!(first.contains(&"hallway-section-2".to_string()));
+        assert!(first.contains(&"room-00".to_string()));
+
+        let second = door_targets(&floor_plan, "hallway-section-2");
+        assert_eq!(second.len(), 12); // ten rooms, previous and next
+        assert!(second.contains(&"hallway".to_string()));
+        assert!(second.contains(&"room-10".to_string()));
+
+        let third = door_targets(&floor_plan, "hallway-section-3");
+        assert_eq!(third.len(), 6);
+        assert!(third.contains(&"room-24".to_string()));
+        assert!(!third.contains(&"hallway-section-4".to_string()));
+
+        // the way back from a moved room leads to its section
+        assert_eq!(
+            door_targets(&floor_plan, "room-24"),
+            vec!["hallway-section-3"]
+        );
+        assert_eq!(door_targets(&floor_plan, "room-00"), vec!["hallway"]);
+        let section = floor_plan.get_room_by_id("hallway-section-3").unwrap();
+        assert_eq!(section.name, "Hallway (Room 20 .. Room 24)");
+    }
+
+    #[test]
+    fn test_partition_large_rooms_by_group() {
+        let mut floor_plan = hallway_floor_plan(6);
+
+        floor_plan
+            .partition_large_rooms(4, |room| room.attributes.get("team").cloned())
+            .unwrap();
+
+        assert_eq!(
+            door_targets(&floor_plan, "hallway"),
+            vec![
+                "hallway-section-2",
+                "lobby",
+                "room-00",
+                "room-02",
+                "room-04"
+            ]
+        );
+        assert_eq!(
+            door_targets(&floor_plan, "hallway-section-2"),
+            vec!["hallway", "room-01", "room-03", "room-05"]
+        );
+        let section = floor_plan.get_room_by_id("hallway-section-2").unwrap();
+        assert_eq!(section.name, "Hallway (1)");
+    }
+
+    #[test]
+    fn test_partition_leaves_small_rooms_alone() {
+        let mut floor_plan = hallway_floor_plan(5);
+        let size = floor_plan.get_world_size();
+
+        floor_plan.partition_large_rooms(10, |_| None).unwrap();
+
+        assert_eq!(floor_plan.get_world_size(), size);
+    }
 }
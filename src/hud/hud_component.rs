@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+/// how the game is getting on with the outside world it builds rooms from
+#[derive(Debug, Clone, Default, Eq, PartialEq, Resource)]
+pub enum ConnectionStatus {
+    #[default]
+    Local, // the world doesn't come from a live source, nothing to show
+    Connecting,
+    Connected,
+    Partial(Vec<String>), // connected, but not allowed to see these parts of the world
+    Retrying {
+        attempt: u32,
+        wait_secs: u64,
+    },
+}
+
+#[derive(Debug, Component)]
+pub struct ConnectionStatusText;
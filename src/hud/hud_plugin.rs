@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use super::hud_component::ConnectionStatus;
+use super::hud_systems::{spawn_hud, update_connection_status};
+use crate::schedule::InGameSet;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConnectionStatus>()
+            .add_systems(Startup, spawn_hud)
+            .add_systems(Update, update_connection_status.in_set(InGameSet::Render));
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::color::palettes::tailwind::{AMBER_500, GREEN_600, RED_600, SLATE_400};
+use bevy::prelude::*;
+
+use super::hud_component::{ConnectionStatus, ConnectionStatusText};
+
+const HUD_FONT_SIZE: f32 = 16.0;
+const HUD_MARGIN: f32 = 10.0;
+
+pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            font_size: HUD_FONT_SIZE,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(HUD_MARGIN),
+            right: Val::Px(HUD_MARGIN),
+            ..default()
+        },
+        ConnectionStatusText,
+    ));
+}
+
+fn connection_status_text(status: &ConnectionStatus) -> (String, Color) {
+    match status {
+        ConnectionStatus::Local => (String::new(), SLATE_400.into()),
+        ConnectionStatus::Connecting => ("connecting...".to_string(), SLATE_400.into()),
+        ConnectionStatus::Connected => ("connected".to_string(), GREEN_600.into()),
+        ConnectionStatus::Partial(forbidden) => (
+            format!("connected - {} forbidden", forbidden.len()),
+            AMBER_500.into(),
+        ),
+        ConnectionStatus::Retrying { attempt, wait_secs } => (
+            format!("disconnected - retry {attempt} in {wait_secs}s"),
+            RED_600.into(),
+        ),
+    }
+}
+
+pub fn update_connection_status(
+    status: Res<ConnectionStatus>,
+    mut query: Query<(&mut Text, &mut TextColor), With<ConnectionStatusText>>,
+) {
+    if !status.is_changed() {
+        return;
+    }
+    let (text, color) = connection_status_text(&status);
+    for (mut status_text, mut status_color) in &mut query {
+        status_text.0.clone_from(&text);
+        status_color.0 = color;
+    }
+}
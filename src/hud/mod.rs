@@ -0,0 +1,6 @@
+pub mod hud_component;
+pub mod hud_plugin;
+pub mod hud_systems;
+
+pub use hud_component::ConnectionStatus;
+pub use hud_plugin::HudPlugin;
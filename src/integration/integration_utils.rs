@@ -1,9 +1,19 @@
-#[derive(Debug, Clone, Eq, PartialEq)]
+use std::collections::BTreeMap;
+
+use crate::floorplan::RoomHealth;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct IntegrationResource {
     pub name: String,
     pub kind: String,
     pub parent: Option<Box<Self>>,
     pub children: Vec<Self>,
+    pub labels: BTreeMap<String, String>,
+    pub selector: Option<BTreeMap<String, String>>, // label selector for the pods this resource targets
+    pub references: Vec<Self>, // other resources this one points at by name, ie: ingress backends
+    pub attributes: BTreeMap<String, String>, // details worth showing in the room, ie: node capacity
+    pub health: RoomHealth,
+    pub created: Option<String>, // creation timestamp, rfc 3339 so it sorts as text
 }
 
 impl IntegrationResource {
@@ -13,6 +23,20 @@ impl IntegrationResource {
             kind,
             parent: parent.map(Box::new),
             children,
+            ..Default::default()
         }
     }
+
+    /**
+     * true if this resource has a selector and every selector term matches a label on the other
+     * resource - services without a selector have none and select nothing, a network policy's empty
+     * pod selector is an empty selector and selects every pod
+     */
+    pub fn selects(&self, other: &Self) -> bool {
+        self.selector.as_ref().is_some_and(|selector| {
+            selector
+                .iter()
+                .all(|(key, value)| other.labels.get(key) == Some(value))
+        })
+    }
 }
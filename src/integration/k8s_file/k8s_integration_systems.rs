@@ -1,17 +1,50 @@
 use std::fs;
 
-use crate::floorplan::{DoorData, FloorPlan, FloorPlanEvent, FloorPlanResult, RoomData};
+use crate::floorplan::{
+    DoorData, FloorPlan, FloorPlanEvent, FloorPlanResult, RoomData, RoomResource,
+};
+use crate::integration::integration_utils::IntegrationResource;
 use bevy::prelude::*;
 use serde_json::json;
 use serde_yaml::Value;
 
-use super::k8s_json::{get_names, get_namespaces};
+use super::k8s_json::{
+    CLUSTER_SCOPED_KINDS, NAMESPACED_KINDS, get_names, get_namespaces, is_cluster_scoped, pluralize,
+};
 
+/**
+* a door from `room1` into `room2` and one back out - the way back is the exit, so `room1` should be
+* the one nearer the lobby
+*/
 pub fn connect_rooms_with_doors(
     plan: &mut FloorPlan,
     room1: &RoomData,
     room2: &RoomData,
     door_id: &mut usize,
+) -> FloorPlanResult<()> {
+    add_door_pair(plan, room1, room2, door_id, true)
+}
+
+/**
+* doors both ways between rooms that are related but neither is on the way to the other, ie: a
+* service and the pods it selects or a pod and its owner, whose way out is through their hallways -
+* neither door is an exit
+*/
+pub fn connect_rooms_side_by_side(
+    plan: &mut FloorPlan,
+    room1: &RoomData,
+    room2: &RoomData,
+    door_id: &mut usize,
+) -> FloorPlanResult<()> {
+    add_door_pair(plan, room1, room2, door_id, false)
+}
+
+fn add_door_pair(
+    plan: &mut FloorPlan,
+    room1: &RoomData,
+    room2: &RoomData,
+    door_id: &mut usize,
+    way_back_is_exit: bool,
 ) -> FloorPlanResult<()> {
     debug!("Connecting rooms with doors");
     let door1 = DoorData {
@@ -29,7 +62,7 @@ pub fn connect_rooms_with_doors(
     let door2 = DoorData {
         id: door_id.to_string(),
         name: format!("Door to {}", room1.name),
-        is_exit: true, // second door is always the way out
+        is_exit: way_back_is_exit,
     };
     *door_id += 1;
     plan.add_door(
@@ -42,6 +75,119 @@ pub fn connect_rooms_with_doors(
     Ok(())
 }
 
+/**
+* cluster scoped resources get their rooms in the cluster lobby's hallways no matter which namespace
+* refers to them
+*/
+pub fn resource_room_id(namespace: &str, kind: &str, name: &str) -> String {
+    let scope = if is_cluster_scoped(kind) {
+        "cluster"
+    } else {
+        namespace
+    };
+    format!("{scope}-{kind}-{name}")
+}
+
+/**
+* what a resource room stands for in the cluster, so the room can be asked about its events and logs
+*/
+pub fn room_resource(namespace: &str, r: &IntegrationResource) -> RoomResource {
+    RoomResource {
+        kind: r.kind.clone(),
+        namespace: (!is_cluster_scoped(&r.kind)).then(|| namespace.to_string()),
+        name: r.name.clone(),
+        owner: r.parent.as_ref().map(|p| format!("{} {}", p.kind, p.name)),
+        labels: r.labels.clone(),
+        created: r.created.clone(),
+    }
+}
+
+pub fn container_room_id(
+    namespace: &str,
+    r: &IntegrationResource,
+    container: &IntegrationResource,
+) -> String {
+    format!(
+        "{}-{}-{}",
+        resource_room_id(namespace, &r.kind, &r.name),
+        "container",
+        container.name
+    )
+}
+
+pub fn volume_mount_room_id(
+    namespace: &str,
+    r: &IntegrationResource,
+    container: &IntegrationResource,
+    volume_mount: &IntegrationResource,
+) -> String {
+    format!(
+        "{}-{}",
+        container_room_id(namespace, r, container),
+        volume_mount.name
+    )
+}
+
+fn connect_to_related_room(
+    plan: &mut FloorPlan,
+    room_id: &str,
+    namespace: &str,
+    target: &IntegrationResource,
+    door_id_generator: &mut usize,
+) -> FloorPlanResult<()> {
+    let target_room_id = resource_room_id(namespace, &target.kind, &target.name);
+    if let (Ok(room), Ok(target_room)) = (
+        plan.get_room_by_id(room_id).cloned(),
+        plan.get_room_by_id(&target_room_id).cloned(),
+    ) {
+        connect_rooms_side_by_side(plan, &room, &target_room, door_id_generator)
+    } else {
+        debug!("Related room not found: {target_room_id}");
+        Ok(())
+    }
+}
+
+/**
+* doors that follow the traffic and the data rather than the ownership - services and network
+* policies to the pods their selectors match, ingresses to their backend services, autoscalers to
+* their scale targets, pods to the nodes they are scheduled on, volume mounts to the config maps,
+* secrets and claims behind them and claims on to their volumes and storage classes.  call once all
+* the rooms of a namespace exist.
+*/
+pub fn connect_related_rooms(
+    plan: &mut FloorPlan,
+    namespace: &str,
+    resources: &[IntegrationResource],
+    door_id_generator: &mut usize,
+) -> FloorPlanResult<()> {
+    for r in resources {
+        let room_id = resource_room_id(namespace, &r.kind, &r.name);
+        let selected = resources
+            .iter()
+            .filter(|target| target.kind == "Pod" && r.selects(target));
+        for target in selected.chain(&r.references) {
+            connect_to_related_room(plan, &room_id, namespace, target, door_id_generator)?;
+        }
+
+        for container in &r.children {
+            for volume_mount in &container.children {
+                let volume_mount_room_id =
+                    volume_mount_room_id(namespace, r, container, volume_mount);
+                for target in &volume_mount.references {
+                    connect_to_related_room(
+                        plan,
+                        &volume_mount_room_id,
+                        namespace,
+                        target,
+                        door_id_generator,
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn add_rooms(
     plan: &mut FloorPlan,
     json_value: &serde_json::Value,
@@ -49,54 +195,56 @@ fn add_rooms(
     outer_room: &RoomData,
     door_id_generator: &mut usize,
     kind: &str,
-) -> FloorPlanResult<()> {
+) -> FloorPlanResult<Vec<IntegrationResource>> {
     if let Ok(resources) = get_names(json_value, kind, namespace) {
-        for r in resources {
+        for r in &resources {
             let room = RoomData {
-                id: format!("{namespace}-{}-{}", r.kind, r.name),
+                id: resource_room_id(namespace, &r.kind, &r.name),
                 name: format!("{} {}", r.kind, r.name),
+                attributes: r.attributes.clone(),
+                health: r.health,
+                resource: Some(room_resource(namespace, r)),
             };
             plan.add_room(room.clone());
-            connect_rooms_with_doors(plan, &room, outer_room, door_id_generator)?;
+            connect_rooms_with_doors(plan, outer_room, &room, door_id_generator)?;
 
             // if there is any parent, connect the room to the parent
-            if let Some(parent) = r.parent {
-                let parent_room_id = format!("{namespace}-{}-{}", parent.kind, parent.name);
+            if let Some(parent) = &r.parent {
+                let parent_room_id = resource_room_id(namespace, &parent.kind, &parent.name);
                 let cplan = plan.clone(); //todo: is this really necessary?
                 let parent_room = cplan.get_room_by_id(&parent_room_id);
                 if let Ok(parent_room) = parent_room {
-                    connect_rooms_with_doors(plan, &room, parent_room, door_id_generator)?;
+                    connect_rooms_side_by_side(plan, parent_room, &room, door_id_generator)?;
                 } else {
                     warn!("Owner room not found: {parent_room_id}");
                 }
             }
 
-            for container in r.children {
+            for container in &r.children {
                 let container_room = RoomData {
-                    id: format!("{namespace}-{}-{}-{}", r.kind, "container", container.name),
+                    id: container_room_id(namespace, r, container),
                     name: format!("{} {}", "container", container.name),
+                    ..Default::default()
                 };
                 plan.add_room(container_room.clone());
-                connect_rooms_with_doors(plan, &container_room, &room, door_id_generator)?;
-                for volume_mount in container.children {
+                connect_rooms_with_doors(plan, &room, &container_room, door_id_generator)?;
+                for volume_mount in &container.children {
                     let volume_mount_room = RoomData {
-                        id: format!(
-                            "{namespace}-{}-{}-{}-{}",
-                            r.kind, "container", container.name, volume_mount.name
-                        ),
+                        id: volume_mount_room_id(namespace, r, container, volume_mount),
                         name: format!("{} {}", "volume mount", volume_mount.name),
+                        ..Default::default()
                     };
                     plan.add_room(volume_mount_room.clone());
                     connect_rooms_with_doors(
                         plan,
-                        &volume_mount_room,
                         &container_room,
+                        &volume_mount_room,
                         door_id_generator,
                     )?;
                 }
             }
         }
-        Ok(())
+        Ok(resources)
     } else {
         Err(crate::floorplan::FloorPlanError::RoomDataNotFound(
             "no resources".to_string(),
@@ -111,10 +259,11 @@ fn setup_hallway_and_rooms(
     outer_room: &RoomData, // will often be the cluster lobby
     door_id_generator: &mut usize,
     kind: &str, // hallways collect similar resources
-) -> FloorPlanResult<()> {
+) -> FloorPlanResult<Vec<IntegrationResource>> {
     let hallway = RoomData {
-        id: format!("{namespace}-{kind}s"),
-        name: format!("{namespace} {kind}s Hallway"),
+        id: format!("{namespace}-{}", pluralize(kind)),
+        name: format!("{namespace} {} Hallway", pluralize(kind)),
+        ..Default::default()
     };
     plan.add_room(hallway.clone());
     connect_rooms_with_doors(plan, outer_room, &hallway, door_id_generator)?;
@@ -125,11 +274,10 @@ fn setup_hallway_and_rooms(
         &hallway,
         door_id_generator,
         kind,
-    )?;
-    Ok(())
+    )
 }
 
-fn generate_k8s_floorplan_from_file() -> FloorPlanResult<FloorPlan> {
+pub fn generate_k8s_floorplan_from_file() -> FloorPlanResult<FloorPlan> {
     let mut floorplan = FloorPlan::new();
     if let Ok(yaml_content) = fs::read_to_string("assets/k8s.yaml") {
         if let Ok(yaml_value) = serde_yaml::from_str::<Value>(&yaml_content) {
@@ -138,15 +286,30 @@ fn generate_k8s_floorplan_from_file() -> FloorPlanResult<FloorPlan> {
             let cluster_room = RoomData {
                 id: "cluster".to_string(),
                 name: "Cluster Lobby".to_string(),
+                ..Default::default()
             };
             floorplan.add_room(cluster_room.clone());
 
             let mut door_id = 0;
+            let mut cluster_resources = Vec::new();
+            for kind in CLUSTER_SCOPED_KINDS {
+                cluster_resources.extend(setup_hallway_and_rooms(
+                    &mut floorplan,
+                    &json_value,
+                    "cluster",
+                    &cluster_room,
+                    &mut door_id,
+                    kind,
+                )?);
+            }
+            connect_related_rooms(&mut floorplan, "cluster", &cluster_resources, &mut door_id)?;
+
             if let Ok(namespaces) = get_namespaces(&json_value) {
                 for namespace in namespaces {
                     let namespace_room = RoomData {
                         id: namespace.clone(),
                         name: format!("{namespace} NS Hallway"),
+                        ..Default::default()
                     };
                     floorplan.add_room(namespace_room.clone());
                     connect_rooms_with_doors(
@@ -156,23 +319,18 @@ fn generate_k8s_floorplan_from_file() -> FloorPlanResult<FloorPlan> {
                         &mut door_id,
                     )?;
 
-                    for kind in &[
-                        "Deployments",
-                        "DaemonSets",
-                        "ReplicaSets",
-                        "Services",
-                        "ConfigMaps",
-                        "Pod",
-                    ] {
-                        setup_hallway_and_rooms(
+                    let mut resources = Vec::new();
+                    for kind in NAMESPACED_KINDS {
+                        resources.extend(setup_hallway_and_rooms(
                             &mut floorplan,
                             &json_value,
                             &namespace,
                             &namespace_room,
                             &mut door_id,
                             kind,
-                        )?;
+                        )?);
                     }
+                    connect_related_rooms(&mut floorplan, &namespace, &resources, &mut door_id)?;
                 }
             }
         } else {
@@ -1,8 +1,54 @@
-use std::{collections::HashSet, error::Error};
+use std::{
+    collections::{BTreeMap, HashSet},
+    error::Error,
+};
 
 use jsonpath_lib::select;
 
-use crate::integration::integration_utils::IntegrationResource;
+use crate::{floorplan::RoomHealth, integration::integration_utils::IntegrationResource};
+
+/**
+* kinds that do not live in a namespace - their rooms hang off hallways in the cluster lobby
+*/
+pub const CLUSTER_SCOPED_KINDS: &[&str] = &["Node", "PersistentVolume", "StorageClass"];
+
+/**
+* a hallway per kind in every namespace.  owners are listed before the kinds they own so the doors
+* to the parent rooms can be made as the children are added.
+*/
+pub const NAMESPACED_KINDS: &[&str] = &[
+    "Deployment",
+    "StatefulSet",
+    "DaemonSet",
+    "CronJob",
+    "Job",
+    "ReplicaSet",
+    "HorizontalPodAutoscaler",
+    "Service",
+    "ConfigMap",
+    "Secret",
+    "PersistentVolumeClaim",
+    "Ingress",
+    "NetworkPolicy",
+    "Pod",
+];
+
+pub fn is_cluster_scoped(kind: &str) -> bool {
+    CLUSTER_SCOPED_KINDS.contains(&kind)
+}
+
+/**
+* ie: Ingress -> Ingresses, `NetworkPolicy` -> `NetworkPolicies`
+*/
+pub fn pluralize(kind: &str) -> String {
+    if kind.ends_with('s') {
+        format!("{kind}es")
+    } else if let Some(stem) = kind.strip_suffix('y') {
+        format!("{stem}ies")
+    } else {
+        format!("{kind}s")
+    }
+}
 
 pub fn get_namespaces(json_value: &serde_json::Value) -> Result<Vec<String>, Box<dyn Error>> {
     let namespaces: HashSet<String> = select(json_value, "$..metadata.namespace")?
@@ -27,7 +73,52 @@ fn get_owner_reference(v: &serde_json::Value) -> Option<(String, String)> {
         })
 }
 
-fn get_volume_mounts(container: &serde_json::Value) -> Vec<IntegrationResource> {
+/**
+* the resources backing each named volume in a pod spec - secrets are only ever referenced by
+* name, their data is never read
+*/
+pub fn get_volume_sources(v: &serde_json::Value) -> BTreeMap<String, Vec<IntegrationResource>> {
+    v["spec"]["volumes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|volume| {
+            let volume_name = volume["name"].as_str()?;
+            let projected = volume["projected"]["sources"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .flat_map(|source| {
+                    [
+                        ("ConfigMap", &source["configMap"]["name"]),
+                        ("Secret", &source["secret"]["name"]),
+                    ]
+                });
+            let sources = [
+                ("ConfigMap", &volume["configMap"]["name"]),
+                ("Secret", &volume["secret"]["secretName"]),
+                (
+                    "PersistentVolumeClaim",
+                    &volume["persistentVolumeClaim"]["claimName"],
+                ),
+            ]
+            .into_iter()
+            .chain(projected)
+            .filter_map(|(kind, name)| {
+                name.as_str().map(|name| {
+                    IntegrationResource::new(name.to_string(), kind.to_string(), None, Vec::new())
+                })
+            })
+            .collect();
+            Some((volume_name.to_string(), sources))
+        })
+        .collect()
+}
+
+pub fn get_volume_mounts(
+    container: &serde_json::Value,
+    volume_sources: &BTreeMap<String, Vec<IntegrationResource>>,
+) -> Vec<IntegrationResource> {
     container["volumeMounts"]
         .as_array()
         .map(|volume_mounts| {
@@ -35,10 +126,13 @@ fn get_volume_mounts(container: &serde_json::Value) -> Vec<IntegrationResource>
                 .iter()
                 .filter_map(|volume_mount| {
                     volume_mount["name"].as_str().map(|n| IntegrationResource {
-                        name: n.to_string(),
-                        kind: "VolumeMount".to_string(),
-                        parent: None,
-                        children: Vec::new(),
+                        references: volume_sources.get(n).cloned().unwrap_or_default(),
+                        ..IntegrationResource::new(
+                            n.to_string(),
+                            "VolumeMount".to_string(),
+                            None,
+                            Vec::new(),
+                        )
                     })
                 })
                 .collect()
@@ -47,6 +141,7 @@ fn get_volume_mounts(container: &serde_json::Value) -> Vec<IntegrationResource>
 }
 
 fn get_containers(v: &serde_json::Value) -> Vec<IntegrationResource> {
+    let volume_sources = get_volume_sources(v);
     v["spec"]["containers"]
         .as_array()
         .map(|containers| {
@@ -54,12 +149,9 @@ fn get_containers(v: &serde_json::Value) -> Vec<IntegrationResource> {
                 .iter()
                 .filter_map(|container| {
                     let container_name = container["name"].as_str().map(String::from);
-                    let volume_mounts = get_volume_mounts(container);
-                    container_name.map(|n| IntegrationResource {
-                        name: n,
-                        kind: "Container".to_string(),
-                        parent: None,
-                        children: volume_mounts,
+                    let volume_mounts = get_volume_mounts(container, &volume_sources);
+                    container_name.map(|n| {
+                        IntegrationResource::new(n, "Container".to_string(), None, volume_mounts)
                     })
                 })
                 .collect()
@@ -67,12 +159,205 @@ fn get_containers(v: &serde_json::Value) -> Vec<IntegrationResource> {
         .unwrap_or_default()
 }
 
+fn get_labels(v: &serde_json::Value) -> BTreeMap<String, String> {
+    to_string_map(&v["metadata"]["labels"]).unwrap_or_default()
+}
+
+fn to_string_map(v: &serde_json::Value) -> Option<BTreeMap<String, String>> {
+    v.as_object().map(|map| {
+        map.iter()
+            .filter_map(|(key, value)| value.as_str().map(|s| (key.clone(), s.to_string())))
+            .collect()
+    })
+}
+
+/**
+* the pod label selector of a resource that routes to or governs pods.  a service without a
+* selector selects nothing while an empty network policy pod selector selects every pod.
+* `matchExpressions` are not evaluated, so a policy that uses them gets no doors rather than wrong
+* ones.
+*/
+pub fn get_selector(kind: &str, v: &serde_json::Value) -> Option<BTreeMap<String, String>> {
+    match kind {
+        "Service" => to_string_map(&v["spec"]["selector"]).filter(|selector| !selector.is_empty()),
+        "NetworkPolicy" => {
+            let pod_selector = &v["spec"]["podSelector"];
+            let has_expressions = pod_selector["matchExpressions"]
+                .as_array()
+                .is_some_and(|expressions| !expressions.is_empty());
+            if pod_selector.is_object() && !has_expressions {
+                Some(to_string_map(&pod_selector["matchLabels"]).unwrap_or_default())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/**
+* named references from one resource to another, ie: ingress rules to their backend services, an
+* autoscaler to its scale target or a volume claim to its volume and storage class
+*/
+pub fn get_references(kind: &str, v: &serde_json::Value) -> Vec<IntegrationResource> {
+    let spec = &v["spec"];
+    let mut references: Vec<(&str, String)> = match kind {
+        "Ingress" => {
+            let default_backend = spec["defaultBackend"]["service"]["name"]
+                .as_str()
+                .map(String::from);
+            let rule_backends = spec["rules"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .flat_map(|rule| rule["http"]["paths"].as_array().into_iter().flatten())
+                .filter_map(|path| {
+                    path["backend"]["service"]["name"]
+                        .as_str()
+                        .map(String::from)
+                });
+            default_backend
+                .into_iter()
+                .chain(rule_backends)
+                .map(|name| ("Service", name))
+                .collect()
+        }
+        "PersistentVolumeClaim" => [
+            ("PersistentVolume", &spec["volumeName"]),
+            ("StorageClass", &spec["storageClassName"]),
+        ]
+        .into_iter()
+        .filter_map(|(kind, name)| name.as_str().map(|name| (kind, name.to_string())))
+        .collect(),
+        "HorizontalPodAutoscaler" => {
+            let target = &spec["scaleTargetRef"];
+            match (target["kind"].as_str(), target["name"].as_str()) {
+                (Some(kind), Some(name)) => vec![(kind, name.to_string())],
+                _ => Vec::new(),
+            }
+        }
+        "Pod" => spec["nodeName"]
+            .as_str()
+            .map(|name| vec![("Node", name.to_string())])
+            .unwrap_or_default(),
+        "PersistentVolume" => spec["storageClassName"]
+            .as_str()
+            .map(|name| vec![("StorageClass", name.to_string())])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    references.sort();
+    references.dedup();
+    references
+        .into_iter()
+        .map(|(kind, name)| IntegrationResource::new(name, kind.to_string(), None, Vec::new()))
+        .collect()
+}
+
+/**
+* the details of a resource worth reading while standing in its room, ie: a node's capacity,
+* conditions and taints
+*/
+pub fn get_attributes(kind: &str, v: &serde_json::Value) -> BTreeMap<String, String> {
+    let mut attributes = BTreeMap::new();
+    if kind == "Node" {
+        if let Some(capacity) = to_string_map(&v["status"]["capacity"]) {
+            let capacity: Vec<String> = capacity
+                .iter()
+                .map(|(resource, quantity)| format!("{resource} {quantity}"))
+                .collect();
+            attributes.insert("capacity".to_string(), capacity.join(", "));
+        }
+        let conditions: Vec<String> = v["status"]["conditions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|condition| {
+                let condition_type = condition["type"].as_str()?;
+                let status = condition["status"].as_str()?;
+                Some(format!("{condition_type}={status}"))
+            })
+            .collect();
+        if !conditions.is_empty() {
+            attributes.insert("conditions".to_string(), conditions.join(", "));
+        }
+        let taints: Vec<String> = v["spec"]["taints"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|taint| {
+                let key = taint["key"].as_str()?;
+                let effect = taint["effect"].as_str().unwrap_or_default();
+                Some(taint["value"].as_str().map_or_else(
+                    || format!("{key}:{effect}"),
+                    |value| format!("{key}={value}:{effect}"),
+                ))
+            })
+            .collect();
+        attributes.insert(
+            "taints".to_string(),
+            if taints.is_empty() {
+                "none".to_string()
+            } else {
+                taints.join(", ")
+            },
+        );
+    }
+    attributes
+}
+
+/**
+* the health of a pod from its status - a container stuck waiting on a crash or a bad image is
+* failing even while the pod phase still says running
+*/
+pub fn get_health(kind: &str, v: &serde_json::Value) -> RoomHealth {
+    const FAILING_REASONS: &[&str] = &[
+        "CrashLoopBackOff",
+        "ImagePullBackOff",
+        "ErrImagePull",
+        "CreateContainerConfigError",
+        "RunContainerError",
+    ];
+
+    if kind != "Pod" {
+        return RoomHealth::Unknown;
+    }
+    let status = &v["status"];
+    let failing = ["initContainerStatuses", "containerStatuses"]
+        .iter()
+        .filter_map(|key| status[key].as_array())
+        .flatten()
+        .any(|container_status| {
+            container_status["state"]["waiting"]["reason"]
+                .as_str()
+                .is_some_and(|reason| FAILING_REASONS.contains(&reason))
+        });
+    let ready = status["conditions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|condition| condition["type"] == "Ready" && condition["status"] == "True");
+
+    match status["phase"].as_str() {
+        _ if failing => RoomHealth::Failing,
+        Some("Failed") => RoomHealth::Failing,
+        Some("Succeeded") => RoomHealth::Ready,
+        Some("Running") if ready => RoomHealth::Ready,
+        Some("Running" | "Pending") => RoomHealth::Pending,
+        _ => RoomHealth::Unknown,
+    }
+}
+
 pub fn get_names(
     json_value: &serde_json::Value,
     kind: &str,
     namespace: &str,
 ) -> Result<Vec<IntegrationResource>, Box<dyn Error>> {
-    let query = format!("$..[?(@.kind == '{kind}' && @.metadata.namespace == '{namespace}')]");
+    let query = if is_cluster_scoped(kind) {
+        format!("$..[?(@.kind == '{kind}')]")
+    } else {
+        format!("$..[?(@.kind == '{kind}' && @.metadata.namespace == '{namespace}')]")
+    };
 
     let resource: Vec<IntegrationResource> = select(json_value, &query)?
         .iter()
@@ -82,7 +367,17 @@ pub fn get_names(
             let containers = get_containers(v);
             let owner = owner_reference
                 .map(|(kind, name)| IntegrationResource::new(name, kind, None, Vec::new()));
-            name.map(|n| IntegrationResource::new(n, kind.to_string(), owner, containers))
+            name.map(|n| IntegrationResource {
+                labels: get_labels(v),
+                created: v["metadata"]["creationTimestamp"]
+                    .as_str()
+                    .map(String::from),
+                selector: get_selector(kind, v),
+                references: get_references(kind, v),
+                attributes: get_attributes(kind, v),
+                health: get_health(kind, v),
+                ..IntegrationResource::new(n, kind.to_string(), owner, containers)
+            })
         })
         .collect();
 
@@ -181,6 +476,96 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_names_created() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "Pod",
+                    "metadata": {
+                        "name": "pod1",
+                        "namespace": "default",
+                        "creationTimestamp": "2025-01-18T14:30:26Z"
+                    }
+                },
+                {
+                    "kind": "Pod",
+                    "metadata": {
+                        "name": "pod2",
+                        "namespace": "default"
+                    }
+                }
+            ]
+        });
+
+        let result = get_names(&json_value, "Pod", "default").unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].created.as_deref(), Some("2025-01-18T14:30:26Z"));
+        assert!(result[1].created.is_none());
+    }
+
+    #[test]
+    fn test_get_names_job_owned_by_cronjob() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "Job",
+                    "metadata": {
+                        "name": "nightly-29000000",
+                        "namespace": "default",
+                        "ownerReferences": [
+                            {
+                                "kind": "CronJob",
+                                "name": "nightly"
+                            }
+                        ]
+                    }
+                }
+            ]
+        });
+
+        let result = get_names(&json_value, "Job", "default").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].kind, "Job");
+        let parent = result[0].parent.as_ref().unwrap();
+        assert_eq!(parent.name, "nightly");
+        assert_eq!(parent.kind, "CronJob");
+    }
+
+    #[test]
+    fn test_get_names_pod_owned_by_statefulset() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "Pod",
+                    "metadata": {
+                        "name": "db-0",
+                        "namespace": "default",
+                        "ownerReferences": [
+                            {
+                                "kind": "StatefulSet",
+                                "name": "db"
+                            }
+                        ]
+                    },
+                    "spec": {
+                        "containers": [
+                            {
+                                "name": "postgres"
+                            }
+                        ]
+                    }
+                }
+            ]
+        });
+
+        let result = get_names(&json_value, "Pod", "default").unwrap();
+        assert_eq!(result.len(), 1);
+        let parent = result[0].parent.as_ref().unwrap();
+        assert_eq!(parent.name, "db");
+        assert_eq!(parent.kind, "StatefulSet");
+    }
+
     #[test]
     fn test_get_names_without_parent_and_containers() {
         let json_value = json!({
@@ -204,4 +589,360 @@ mod tests {
         assert!(service.parent.is_none());
         assert!(service.children.is_empty());
     }
+
+    #[test]
+    fn test_get_names_with_labels_and_selector() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "Service",
+                    "metadata": {
+                        "name": "web",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "selector": {
+                            "app": "web"
+                        }
+                    }
+                },
+                {
+                    "kind": "Pod",
+                    "metadata": {
+                        "name": "web-1",
+                        "namespace": "default",
+                        "labels": {
+                            "app": "web",
+                            "tier": "frontend"
+                        }
+                    }
+                },
+                {
+                    "kind": "Pod",
+                    "metadata": {
+                        "name": "db-1",
+                        "namespace": "default",
+                        "labels": {
+                            "app": "db"
+                        }
+                    }
+                }
+            ]
+        });
+
+        let services = get_names(&json_value, "Service", "default").unwrap();
+        let pods = get_names(&json_value, "Pod", "default").unwrap();
+        assert_eq!(services.len(), 1);
+        assert_eq!(pods.len(), 2);
+
+        let service = &services[0];
+        assert!(service.selects(&pods[0]));
+        assert!(!service.selects(&pods[1]));
+        assert!(!pods[0].selects(service));
+    }
+
+    #[test]
+    fn test_get_names_ingress_backends() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "Ingress",
+                    "metadata": {
+                        "name": "ingress1",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "defaultBackend": {
+                            "service": { "name": "fallback" }
+                        },
+                        "rules": [
+                            {
+                                "http": {
+                                    "paths": [
+                                        { "backend": { "service": { "name": "web" } } },
+                                        { "backend": { "service": { "name": "api" } } },
+                                        { "backend": { "service": { "name": "web" } } }
+                                    ]
+                                }
+                            }
+                        ]
+                    }
+                }
+            ]
+        });
+
+        let result = get_names(&json_value, "Ingress", "default").unwrap();
+        assert_eq!(result.len(), 1);
+
+        let backends: Vec<&str> = result[0]
+            .references
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(backends, vec!["api", "fallback", "web"]);
+        assert!(result[0].references.iter().all(|r| r.kind == "Service"));
+    }
+
+    #[test]
+    fn test_get_names_volume_mount_sources() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "Pod",
+                    "metadata": {
+                        "name": "pod1",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "containers": [
+                            {
+                                "name": "container1",
+                                "volumeMounts": [
+                                    { "name": "config" },
+                                    { "name": "creds" },
+                                    { "name": "data" },
+                                    { "name": "scratch" }
+                                ]
+                            }
+                        ],
+                        "volumes": [
+                            { "name": "config", "configMap": { "name": "app-config" } },
+                            { "name": "creds", "secret": { "secretName": "app-creds" } },
+                            { "name": "data", "persistentVolumeClaim": { "claimName": "app-data" } },
+                            { "name": "scratch", "emptyDir": {} }
+                        ]
+                    }
+                }
+            ]
+        });
+
+        let result = get_names(&json_value, "Pod", "default").unwrap();
+        let volume_mounts = &result[0].children[0].children;
+        assert_eq!(volume_mounts.len(), 4);
+
+        let sources: Vec<Vec<(&str, &str)>> = volume_mounts
+            .iter()
+            .map(|volume_mount| {
+                volume_mount
+                    .references
+                    .iter()
+                    .map(|r| (r.kind.as_str(), r.name.as_str()))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                vec![("ConfigMap", "app-config")],
+                vec![("Secret", "app-creds")],
+                vec![("PersistentVolumeClaim", "app-data")],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_names_storage_references() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "PersistentVolumeClaim",
+                    "metadata": {
+                        "name": "app-data",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "volumeName": "pv-123",
+                        "storageClassName": "fast"
+                    }
+                },
+                {
+                    "kind": "PersistentVolume",
+                    "metadata": {
+                        "name": "pv-123"
+                    },
+                    "spec": {
+                        "storageClassName": "fast"
+                    }
+                }
+            ]
+        });
+
+        let claims = get_names(&json_value, "PersistentVolumeClaim", "default").unwrap();
+        let claim_references: Vec<(&str, &str)> = claims[0]
+            .references
+            .iter()
+            .map(|r| (r.kind.as_str(), r.name.as_str()))
+            .collect();
+        assert_eq!(
+            claim_references,
+            vec![("PersistentVolume", "pv-123"), ("StorageClass", "fast")]
+        );
+
+        // cluster scoped kinds are found regardless of the namespace asked for
+        let volumes = get_names(&json_value, "PersistentVolume", "cluster").unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].references[0].name, "fast");
+    }
+
+    #[test]
+    fn test_get_names_node_attributes_and_pod_placement() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "Node",
+                    "metadata": {
+                        "name": "node1"
+                    },
+                    "spec": {
+                        "taints": [
+                            { "key": "dedicated", "value": "gpu", "effect": "NoSchedule" }
+                        ]
+                    },
+                    "status": {
+                        "capacity": { "cpu": "4", "memory": "8Gi" },
+                        "conditions": [
+                            { "type": "MemoryPressure", "status": "False" },
+                            { "type": "Ready", "status": "True" }
+                        ]
+                    }
+                },
+                {
+                    "kind": "Pod",
+                    "metadata": {
+                        "name": "pod1",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "nodeName": "node1"
+                    }
+                }
+            ]
+        });
+
+        let nodes = get_names(&json_value, "Node", "cluster").unwrap();
+        assert_eq!(nodes.len(), 1);
+        let attributes = &nodes[0].attributes;
+        assert_eq!(attributes["capacity"], "cpu 4, memory 8Gi");
+        assert_eq!(attributes["conditions"], "MemoryPressure=False, Ready=True");
+        assert_eq!(attributes["taints"], "dedicated=gpu:NoSchedule");
+
+        let pods = get_names(&json_value, "Pod", "default").unwrap();
+        assert_eq!(
+            pods[0].references,
+            vec![IntegrationResource::new(
+                "node1".to_string(),
+                "Node".to_string(),
+                None,
+                Vec::new()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_get_names_autoscaler_and_network_policy() {
+        let json_value = json!({
+            "items": [
+                {
+                    "kind": "HorizontalPodAutoscaler",
+                    "metadata": {
+                        "name": "web",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "scaleTargetRef": { "apiVersion": "apps/v1", "kind": "StatefulSet", "name": "web" }
+                    }
+                },
+                {
+                    "kind": "NetworkPolicy",
+                    "metadata": {
+                        "name": "deny-all",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "podSelector": {}
+                    }
+                },
+                {
+                    "kind": "NetworkPolicy",
+                    "metadata": {
+                        "name": "web-only",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "podSelector": { "matchLabels": { "app": "web" } }
+                    }
+                },
+                {
+                    "kind": "NetworkPolicy",
+                    "metadata": {
+                        "name": "expressions",
+                        "namespace": "default"
+                    },
+                    "spec": {
+                        "podSelector": {
+                            "matchExpressions": [
+                                { "key": "app", "operator": "In", "values": ["web"] }
+                            ]
+                        }
+                    }
+                },
+                {
+                    "kind": "Pod",
+                    "metadata": {
+                        "name": "db-0",
+                        "namespace": "default",
+                        "labels": { "app": "db" }
+                    }
+                }
+            ]
+        });
+
+        let autoscalers = get_names(&json_value, "HorizontalPodAutoscaler", "default").unwrap();
+        assert_eq!(
+            autoscalers[0].references,
+            vec![IntegrationResource::new(
+                "web".to_string(),
+                "StatefulSet".to_string(),
+                None,
+                Vec::new()
+            )]
+        );
+
+        let policies = get_names(&json_value, "NetworkPolicy", "default").unwrap();
+        let pods = get_names(&json_value, "Pod", "default").unwrap();
+        let selected: Vec<bool> = policies
+            .iter()
+            .map(|policy| policy.selects(&pods[0]))
+            .collect();
+        assert_eq!(selected, vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_get_health() {
+        let pod = |status: serde_json::Value| json!({ "kind": "Pod", "status": status });
+
+        let crash_looping = pod(json!({
+            "phase": "Running",
+            "containerStatuses": [
+                { "name": "app", "state": { "waiting": { "reason": "CrashLoopBackOff" } } }
+            ]
+        }));
+        let pending = pod(json!({ "phase": "Pending" }));
+        let not_ready = pod(json!({
+            "phase": "Running",
+            "conditions": [ { "type": "Ready", "status": "False" } ]
+        }));
+        let ready = pod(json!({
+            "phase": "Running",
+            "conditions": [ { "type": "Ready", "status": "True" } ]
+        }));
+
+        assert_eq!(get_health("Pod", &crash_looping), RoomHealth::Failing);
+        assert_eq!(get_health("Pod", &pending), RoomHealth::Pending);
+        assert_eq!(get_health("Pod", &not_ready), RoomHealth::Pending);
+        assert_eq!(get_health("Pod", &ready), RoomHealth::Ready);
+        assert_eq!(get_health("Pod", &pod(json!({}))), RoomHealth::Unknown);
+        assert_eq!(get_health("Service", &ready), RoomHealth::Unknown);
+    }
 }
@@ -1,11 +1,20 @@
 use crate::integration::integration_utils::IntegrationResource;
+use crate::integration::k8s_file::k8s_json::{
+    get_attributes, get_health, get_references, get_selector, get_volume_mounts,
+    get_volume_sources, is_cluster_scoped, pluralize,
+};
 use bevy::log::debug;
-use kube::core::{ApiResource, DynamicObject};
+use kube::core::{ApiResource, DynamicObject, ObjectList, PartialObjectMeta};
 use kube::{
     Client,
     api::{Api, ListParams},
 };
+use serde::de::DeserializeOwned;
 use std::error::Error;
+use std::fmt::Debug;
+
+/// listing errors cross threads while lists are fetched side by side
+pub type ListError = Box<dyn Error + Send + Sync>;
 
 /**
 * keep this updated for any 'kind' you want to support - note that different kinds are in different
@@ -13,8 +22,11 @@ use std::error::Error;
 */
 fn get_api_params(kind: &str) -> (&str, &str) {
     match kind {
-        "DaemonSet" | "ReplicaSet" | "Deployment" => ("apps", "v1"),
-        "Ingress" => ("networking.k8s.io", "v1"),
+        "DaemonSet" | "ReplicaSet" | "Deployment" | "StatefulSet" => ("apps", "v1"),
+        "Job" | "CronJob" => ("batch", "v1"),
+        "HorizontalPodAutoscaler" => ("autoscaling", "v2"),
+        "Ingress" | "NetworkPolicy" => ("networking.k8s.io", "v1"),
+        "StorageClass" => ("storage.k8s.io", "v1"),
         _ => ("", "v1"),
     }
 }
@@ -31,22 +43,77 @@ fn build_api_resource(kind: &str, group: &str, version: &str) -> ApiResource {
         version: version.to_string(),
         api_version,
         kind: kind.to_string(),
-        plural: format!("{}s", kind.to_lowercase()),
+        plural: pluralize(kind).to_lowercase(),
+    }
+}
+
+const PAGE_SIZE: u32 = 500; // big clusters are listed a page at a time
+
+/**
+* follow the continue tokens until the api server has nothing more to give, `list_page` fetching
+* the page asked for by the params it is handed
+*/
+async fn list_pages<T, F, Fut>(mut list_page: F) -> kube::Result<Vec<T>>
+where
+    T: Clone,
+    F: FnMut(ListParams) -> Fut,
+    Fut: Future<Output = kube::Result<ObjectList<T>>>,
+{
+    let mut lp = ListParams::default().limit(PAGE_SIZE);
+    let mut items = Vec::new();
+    loop {
+        let page = list_page(lp.clone()).await?;
+        items.extend(page.items);
+        match page.metadata.continue_ {
+            Some(token) if !token.is_empty() => lp = lp.continue_token(&token),
+            _ => return Ok(items),
+        }
     }
 }
 
+/**
+* every object of the api's kind, a page at a time
+*/
+pub async fn list_all<K>(api: &Api<K>) -> kube::Result<Vec<K>>
+where
+    K: Clone + DeserializeOwned + Debug,
+{
+    list_pages(|lp| async move { api.list(&lp).await }).await
+}
+
+async fn list_all_metadata<K>(api: &Api<K>) -> kube::Result<Vec<PartialObjectMeta<K>>>
+where
+    K: Clone + DeserializeOwned + Debug,
+{
+    list_pages(|lp| async move { api.list_metadata(&lp).await }).await
+}
+
+/**
+* cluster scoped kinds are listed without a namespace.  secrets are listed as metadata only so
+* their data never leaves the cluster.
+*/
 async fn fetch_resource_list(
     client: &Client,
     namespace: &str,
     resource: &ApiResource,
-) -> Result<Vec<DynamicObject>, Box<dyn Error>> {
-    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), namespace, resource);
-    let lp = ListParams::default();
-    let resource_list = api
-        .list(&lp)
-        .await
-        .map_err(|e| format!("Failed to list resources: {e}"))?;
-    Ok(resource_list.items)
+) -> Result<Vec<DynamicObject>, ListError> {
+    let api: Api<DynamicObject> = if is_cluster_scoped(&resource.kind) {
+        Api::all_with(client.clone(), resource)
+    } else {
+        Api::namespaced_with(client.clone(), namespace, resource)
+    };
+    if resource.kind == "Secret" {
+        return Ok(list_all_metadata(&api)
+            .await?
+            .into_iter()
+            .map(|partial| DynamicObject {
+                types: partial.types,
+                metadata: partial.metadata,
+                data: serde_json::Value::Null,
+            })
+            .collect());
+    }
+    Ok(list_all(&api).await?)
 }
 
 /**
@@ -64,12 +131,19 @@ fn extract_integration_resources(
             let containers = get_containers(&resource);
             let owner = owner_reference
                 .map(|(kind, name)| IntegrationResource::new(name, kind, None, Vec::new()));
-            resources.push(IntegrationResource::new(
-                name,
-                kind.to_string(),
-                owner,
-                containers,
-            ));
+            resources.push(IntegrationResource {
+                labels: resource.metadata.labels.clone().unwrap_or_default(),
+                selector: get_selector(kind, &resource.data),
+                references: get_references(kind, &resource.data),
+                attributes: get_attributes(kind, &resource.data),
+                health: get_health(kind, &resource.data),
+                created: resource
+                    .metadata
+                    .creation_timestamp
+                    .as_ref()
+                    .map(|t| t.0.to_string()),
+                ..IntegrationResource::new(name, kind.to_string(), owner, containers)
+            });
         }
     }
     resources
@@ -87,6 +161,7 @@ fn get_owner_reference(v: &DynamicObject) -> Option<(String, String)> {
 }
 
 fn get_containers(v: &DynamicObject) -> Vec<IntegrationResource> {
+    let volume_sources = get_volume_sources(&v.data);
     v.data["spec"]["containers"]
         .as_array()
         .map(|containers| {
@@ -94,12 +169,9 @@ fn get_containers(v: &DynamicObject) -> Vec<IntegrationResource> {
                 .iter()
                 .filter_map(|container| {
                     let container_name = container["name"].as_str().map(String::from);
-                    let volume_mounts = get_volume_mounts(container);
-                    container_name.map(|n| IntegrationResource {
-                        name: n,
-                        kind: "Container".to_string(),
-                        parent: None,
-                        children: volume_mounts,
+                    let volume_mounts = get_volume_mounts(container, &volume_sources);
+                    container_name.map(|n| {
+                        IntegrationResource::new(n, "Container".to_string(), None, volume_mounts)
                     })
                 })
                 .collect()
@@ -107,23 +179,11 @@ fn get_containers(v: &DynamicObject) -> Vec<IntegrationResource> {
         .unwrap_or_default()
 }
 
-fn get_volume_mounts(container: &serde_json::Value) -> Vec<IntegrationResource> {
-    container["volumeMounts"]
-        .as_array()
-        .map(|volume_mounts| {
-            volume_mounts
-                .iter()
-                .filter_map(|volume_mount| {
-                    volume_mount["name"].as_str().map(|n| IntegrationResource {
-                        name: n.to_string(),
-                        kind: "VolumeMount".to_string(),
-                        parent: None,
-                        children: Vec::new(),
-                    })
-                })
-                .collect()
-        })
-        .unwrap_or_default()
+/**
+* rbac said no - the rest of the cluster may still be worth showing
+*/
+pub fn is_forbidden(e: &(dyn Error + 'static)) -> bool {
+    matches!(e.downcast_ref::<kube::Error>(), Some(kube::Error::Api(status)) if status.code == 403)
 }
 
 /**
@@ -134,7 +194,7 @@ pub async fn get_names(
     client: &Client,
     kind: &str,
     namespace: &str,
-) -> Result<Vec<IntegrationResource>, Box<dyn Error>> {
+) -> Result<Vec<IntegrationResource>, ListError> {
     debug!("Getting names for {kind} in {namespace}");
 
     let (group, version) = get_api_params(kind);
@@ -146,121 +206,183 @@ pub async fn get_names(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bevy::log::{debug, info};
-    use kube::Client;
+    use crate::integration::k8s_live::k8s_mock::{MockApiServer, named, pod};
+    use k8s_openapi::api::core::v1::Namespace;
+    use serde_json::json;
+    use std::collections::BTreeMap;
 
     #[tokio::test]
     async fn test_list_namespaces() {
-        let client = Client::try_default()
+        let (client, server) = MockApiServer::new()
+            .with_pages(
+                "/api/v1/namespaces",
+                "NamespaceList",
+                vec![
+                    vec![named("default"), named("kube-system")],
+                    vec![named("monitoring")],
+                ],
+            )
+            .start();
+        let namespaces: Api<Namespace> = Api::all(client);
+
+        let names: Vec<String> = list_all(&namespaces)
             .await
-            .expect("Failed to create client");
-        let namespaces: Api<k8s_openapi::api::core::v1::Namespace> = Api::all(client);
-
-        match namespaces.list(&ListParams::default()).await {
-            Ok(ns_list) => {
-                println!("Found {} namespaces", ns_list.items.len());
-                for ns in ns_list {
-                    println!("Namespace: {}", ns.metadata.name.unwrap_or_default());
-                }
-            }
-            Err(e) => {
-                eprintln!("Error fetching namespaces: {}", e);
-                panic!("Failed to fetch namespaces");
-            }
-        }
+            .expect("namespaces expected")
+            .into_iter()
+            .filter_map(|ns| ns.metadata.name)
+            .collect();
+        drop(namespaces);
+
+        assert_eq!(names, vec!["default", "kube-system", "monitoring"]);
+        assert_eq!(server.await.unwrap().len(), 2);
     }
 
     #[tokio::test]
     async fn test_get_names_pods() {
-        let client = Client::try_default()
+        let (client, _) = MockApiServer::new()
+            .with_list(
+                "/api/v1/namespaces/kube-system/pods",
+                "PodList",
+                vec![
+                    pod("coredns-1", "coredns", &["coredns"]),
+                    pod("coredns-2", "coredns", &["coredns"]),
+                ],
+            )
+            .start();
+
+        let resources = get_names(&client, "Pod", "kube-system")
             .await
-            .expect("Failed to create client");
-        let namespace = "kube-system";
-        let kind = "Pod";
-
-        match get_names(&client, kind, namespace).await {
-            Ok(resources) => {
-                assert!(!resources.is_empty());
-                println!("Found {} Pods", resources.len());
-                for resource in resources {
-                    println!("Pod: {}", resource.name);
-                }
-            }
-            Err(e) => {
-                eprintln!("Error fetching Pods: {}", e);
-                panic!("Failed to fetch Pods");
-            }
-        }
+            .expect("pods expected");
+
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].name, "coredns-1");
+        assert_eq!(resources[0].children[0].name, "coredns");
+        assert_eq!(
+            resources[0].created.as_deref(),
+            Some("2025-01-18T14:30:26Z")
+        );
+        let owner = resources[0].parent.as_ref().expect("owner expected");
+        assert_eq!(
+            (owner.kind.as_str(), owner.name.as_str()),
+            ("ReplicaSet", "coredns")
+        );
     }
 
     #[tokio::test]
     async fn test_get_names_replicasets() {
-        info!("info");
-        debug!("debug");
-        let client = Client::try_default()
+        let (client, server) = MockApiServer::new()
+            .with_list(
+                "/apis/apps/v1/namespaces/kube-system/replicasets",
+                "ReplicaSetList",
+                vec![named("coredns")],
+            )
+            .start();
+
+        let resources = get_names(&client, "ReplicaSet", "kube-system")
             .await
-            .expect("Failed to create client");
-        let namespace = "kube-system";
-        let kind = "ReplicaSet";
-
-        match get_names(&client, kind, namespace).await {
-            Ok(resources) => {
-                assert!(!resources.is_empty());
-                println!("Found {} ReplicaSets", resources.len());
-                for resource in resources {
-                    println!("ReplicaSet: {}", resource.name);
-                }
-            }
-            Err(e) => {
-                eprintln!("Error fetching {kind}: {e}");
-                panic!("Failed to fetch {kind}");
-            }
-        }
+            .expect("replicasets expected");
+        drop(client);
+
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].kind, "ReplicaSet");
+        let requested = server.await.unwrap();
+        assert!(requested[0].starts_with("/apis/apps/v1/namespaces/kube-system/replicasets"));
     }
 
     #[tokio::test]
     async fn test_get_names_services() {
-        let client = Client::try_default()
+        let (client, _) = MockApiServer::new()
+            .with_list(
+                "/api/v1/namespaces/kube-system/services",
+                "ServiceList",
+                vec![json!({
+                    "metadata": { "name": "kube-dns" },
+                    "spec": { "selector": { "k8s-app": "kube-dns" } },
+                })],
+            )
+            .start();
+
+        let resources = get_names(&client, "Service", "kube-system")
             .await
-            .expect("Failed to create client");
-        let namespace = "kube-system";
-        let kind = "Service";
-
-        match get_names(&client, kind, namespace).await {
-            Ok(resources) => {
-                assert!(!resources.is_empty());
-                println!("Found {} Services", resources.len());
-                for resource in resources {
-                    println!("Service: {}", resource.name);
-                }
-            }
-            Err(e) => {
-                eprintln!("Error fetching Services: {}", e);
-                panic!("Failed to fetch Services");
-            }
-        }
+            .expect("services expected");
+
+        assert_eq!(resources.len(), 1);
+        assert_eq!(
+            resources[0].selector,
+            Some(BTreeMap::from([(
+                "k8s-app".to_string(),
+                "kube-dns".to_string()
+            )]))
+        );
     }
 
     #[tokio::test]
     async fn test_get_names_configmaps() {
-        let client = Client::try_default()
+        let (client, _) = MockApiServer::new()
+            .with_list(
+                "/api/v1/namespaces/kube-system/configmaps",
+                "ConfigMapList",
+                vec![named("coredns"), named("kube-proxy")],
+            )
+            .start();
+
+        let resources = get_names(&client, "ConfigMap", "kube-system")
             .await
-            .expect("Failed to create client");
-        let namespace = "kube-system";
-        let kind = "ConfigMap";
-
-        match get_names(&client, kind, namespace).await {
-            Ok(resources) => {
-                assert!(!resources.is_empty());
-                println!("Found {} ConfigMaps", resources.len());
-                for resource in resources {
-                    println!("ConfigMap: {}", resource.name);
-                }
-            }
-            Err(e) => {
-                eprintln!("Error fetching ConfigMaps: {}", e);
-                panic!("Failed to fetch ConfigMaps");
-            }
-        }
+            .expect("configmaps expected");
+
+        let names: Vec<&str> = resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["coredns", "kube-proxy"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_names_cluster_scoped_ignores_namespace() {
+        let (client, _) = MockApiServer::new()
+            .with_list("/api/v1/nodes", "NodeList", vec![named("node-1")])
+            .start();
+
+        let resources = get_names(&client, "Node", "kube-system")
+            .await
+            .expect("nodes expected");
+
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].name, "node-1");
+    }
+
+    #[tokio::test]
+    async fn test_get_names_follows_continue_tokens() {
+        let (client, server) = MockApiServer::new()
+            .with_pages(
+                "/api/v1/namespaces/default/configmaps",
+                "ConfigMapList",
+                vec![
+                    vec![named("a"), named("b")],
+                    vec![named("c")],
+                    vec![named("d")],
+                ],
+            )
+            .start();
+
+        let resources = get_names(&client, "ConfigMap", "default")
+            .await
+            .expect("configmaps expected");
+        drop(client);
+
+        let names: Vec<&str> = resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c", "d"]);
+        let requested = server.await.unwrap();
+        assert_eq!(requested.len(), 3);
+        assert!(requested.iter().all(|uri| uri.contains("limit=500")));
+        assert!(requested[2].contains("continue=2"));
+    }
+
+    #[tokio::test]
+    async fn test_get_names_forbidden() {
+        let (client, _) = MockApiServer::new()
+            .with_error("/api/v1/namespaces/kube-system/secrets", 403, "Forbidden")
+            .start();
+
+        let result = get_names(&client, "Secret", "kube-system").await;
+
+        assert!(result.is_err_and(|e| is_forbidden(e.as_ref())));
     }
 }
@@ -1,11 +1,16 @@
 use bevy::prelude::*;
 
-use super::k8s_integration_systems::init_k8s_live_floorplan_publisher;
+use super::k8s_integration_systems::{K8sClient, init_k8s_live_floorplan_publisher};
+use super::k8s_pod_terminal::{PodTerminalTask, stream_pod_terminal};
+use crate::schedule::InGameSet;
 
 pub struct K8sIntegrationPlugin;
 
 impl Plugin for K8sIntegrationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, init_k8s_live_floorplan_publisher);
+        app.init_resource::<K8sClient>()
+            .init_resource::<PodTerminalTask>()
+            .add_systems(Startup, init_k8s_live_floorplan_publisher)
+            .add_systems(Update, stream_pod_terminal.in_set(InGameSet::Render));
     }
 }
@@ -1,51 +1,165 @@
-use super::k8s_api::get_names;
+use super::k8s_api::{ListError, get_names, is_forbidden, list_all};
 use crate::cli::Cli;
-use crate::floorplan::{FloorPlan, FloorPlanEvent, FloorPlanResult, RoomData};
+use crate::floorplan::{FloorPlan, FloorPlanError, FloorPlanEvent, FloorPlanResult, RoomData};
+use crate::hud::ConnectionStatus;
 use crate::integration::integration_utils::IntegrationResource;
-use crate::integration::k8s_file::k8s_integration_systems::connect_rooms_with_doors;
+use crate::integration::k8s_file::k8s_integration_systems::{
+    connect_related_rooms, connect_rooms_side_by_side, connect_rooms_with_doors, container_room_id,
+    resource_room_id, room_resource, volume_mount_room_id,
+};
+use crate::integration::k8s_file::k8s_json::{CLUSTER_SCOPED_KINDS, NAMESPACED_KINDS, pluralize};
 use bevy::prelude::*;
 use bevy_tokio_tasks::{TaskContext, TokioTasksRuntime};
 use clap::Parser;
-use kube::{
-    Client,
-    api::{Api, ListParams},
-};
+use futures::future::BoxFuture;
+use futures::{FutureExt, StreamExt, stream};
+use k8s_openapi::api::core::v1::Namespace;
+use kube::{Client, api::Api};
+use std::collections::HashMap;
 use std::time::Duration;
 
+const INITIAL_BACKOFF_SECS: u64 = 1;
+const MAX_BACKOFF_SECS: u64 = 60;
+const MAX_CONCURRENT_LISTS: usize = 16; // enough to be quick without hammering the api server
+
+/**
+* the client the publisher last generated a floorplan with, shared so the pod terminal talks to the
+* cluster the same way - none until the first floorplan is published
+*/
+#[derive(Default, Resource)]
+pub struct K8sClient(pub Option<Client>);
+
 async fn create_k8s_client() -> FloorPlanResult<Client> {
     Client::try_default()
         .await
         .map_err(|e| crate::floorplan::FloorPlanError::ServiceError(e.to_string()))
 }
 
+/**
+* a user whose rbac stops at their own namespace can't list namespaces, they get the client's
+* default namespace and the lobby shows the cluster as partial
+*/
 async fn fetch_namespaces(
     client: &Client,
-) -> FloorPlanResult<Vec<k8s_openapi::api::core::v1::Namespace>> {
-    let namespaces: Api<k8s_openapi::api::core::v1::Namespace> = Api::all(client.clone());
-    let lp = ListParams::default();
-    namespaces
-        .list(&lp)
-        .await
-        .map(|ns_list| ns_list.items)
-        .map_err(|e| crate::floorplan::FloorPlanError::ServiceError(e.to_string()))
+    forbidden: &mut Vec<String>,
+) -> FloorPlanResult<Vec<String>> {
+    let namespaces: Api<Namespace> = Api::all(client.clone());
+    match list_all(&namespaces).await {
+        Ok(ns_list) => Ok(ns_list
+            .into_iter()
+            .filter_map(|ns| ns.metadata.name)
+            .collect()),
+        Err(kube::Error::Api(status)) if status.code == 403 => {
+            let namespace = client.default_namespace().to_string();
+            warn!("Not allowed to list namespaces, showing only {namespace}");
+            forbidden.push("cluster/Namespace".to_string());
+            Ok(vec![namespace])
+        }
+        Err(e) => Err(crate::floorplan::FloorPlanError::ServiceError(
+            e.to_string(),
+        )),
+    }
+}
+
+/**
+* everything listed from the cluster, by namespace and kind - cluster scoped kinds are under
+* "cluster"
+*/
+#[derive(Debug, Default)]
+struct Listings {
+    resources: HashMap<(String, String), Vec<IntegrationResource>>,
+    forbidden: Vec<String>, // "namespace/kind" we were not allowed to list
+}
+
+impl Listings {
+    fn get(&self, namespace: &str, kind: &str) -> &[IntegrationResource] {
+        self.resources
+            .get(&(namespace.to_string(), kind.to_string()))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+type Listed = (
+    String,
+    &'static str,
+    Result<Vec<IntegrationResource>, ListError>,
+);
+
+/**
+* a plain fn handing back a boxed future - built inside the async fn the compiler can't prove the
+* stream is Send, which the publisher task needs
+*/
+fn list_concurrently(
+    client: Client,
+    lists: Vec<(String, &'static str)>,
+) -> BoxFuture<'static, Vec<Listed>> {
+    stream::iter(lists)
+        .map(move |(namespace, kind)| {
+            let client = client.clone();
+            async move {
+                let result = get_names(&client, kind, &namespace).await;
+                (namespace, kind, result)
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_LISTS)
+        .collect()
+        .boxed()
+}
+
+/**
+* every list the floorplan needs, a bounded number at a time - kinds we are not allowed to list are
+* left empty, any other failure fails the lot
+*/
+async fn fetch_listings(client: &Client, namespaces: &[String]) -> FloorPlanResult<Listings> {
+    let mut lists: Vec<(String, &'static str)> = CLUSTER_SCOPED_KINDS
+        .iter()
+        .map(|kind| ("cluster".to_string(), *kind))
+        .collect();
+    for namespace in namespaces {
+        lists.extend(
+            NAMESPACED_KINDS
+                .iter()
+                .map(|kind| (namespace.clone(), *kind)),
+        );
+    }
+    let results = list_concurrently(client.clone(), lists).await;
+
+    let mut listings = Listings::default();
+    for (namespace, kind, result) in results {
+        match result {
+            Ok(resources) => {
+                listings
+                    .resources
+                    .insert((namespace, kind.to_string()), resources);
+            }
+            Err(e) if is_forbidden(e.as_ref()) => {
+                warn!("Not allowed to list {kind} in {namespace}, leaving the hallway empty");
+                listings.forbidden.push(format!("{namespace}/{kind}"));
+            }
+            Err(e) => return Err(FloorPlanError::ServiceError(e.to_string())),
+        }
+    }
+    listings.forbidden.sort();
+    Ok(listings)
 }
 
 fn create_cluster_room() -> RoomData {
     RoomData {
         id: "cluster".to_string(),
         name: "Cluster Lobby".to_string(),
+        ..Default::default()
     }
 }
 
 /**
-* for a given namespace, walk through the resources and create rooms for each and add them to the
-* floorplan
+* for a given namespace, walk through the listed resources and create rooms for each and add them
+* to the floorplan
 */
-async fn process_namespace(
+fn process_namespace(
     floorplan: &mut FloorPlan,
     cluster_room: &RoomData,
     door_id: &mut usize,
-    client: &Client,
+    listings: &Listings,
     namespace: &str,
 ) -> FloorPlanResult<()> {
     debug!("processing namespace {namespace}");
@@ -53,18 +167,18 @@ async fn process_namespace(
     floorplan.add_room(namespace_room.clone());
     connect_rooms_with_doors(floorplan, cluster_room, &namespace_room, door_id)?;
 
-    for kind in &[
-        "Deployment",
-        "DaemonSet",
-        "ReplicaSet",
-        "Service",
-        "ConfigMap",
-        "Ingress",
-        "Pod",
-    ] {
-        setup_hallway_and_rooms(floorplan, namespace, &namespace_room, door_id, kind, client)
-            .await?;
+    let mut resources = Vec::new();
+    for kind in NAMESPACED_KINDS {
+        resources.extend(setup_hallway_and_rooms(
+            floorplan,
+            namespace,
+            &namespace_room,
+            door_id,
+            kind,
+            listings,
+        )?);
     }
+    connect_related_rooms(floorplan, namespace, &resources, door_id)?;
 
     Ok(())
 }
@@ -73,64 +187,65 @@ fn create_namespace_room(namespace: &str) -> RoomData {
     RoomData {
         id: namespace.to_string(),
         name: format!("{namespace} NS Hallway"),
+        ..Default::default()
     }
 }
 
-async fn setup_hallway_and_rooms(
+fn setup_hallway_and_rooms(
     plan: &mut FloorPlan,
     namespace: &str,
     outer_room: &RoomData,
     door_id_generator: &mut usize,
     kind: &str,
-    client: &Client,
-) -> FloorPlanResult<()> {
+    listings: &Listings,
+) -> FloorPlanResult<Vec<IntegrationResource>> {
     debug!("Setting up {kind} hallway and rooms");
     let hallway = create_hallway_room(namespace, kind);
     plan.add_room(hallway.clone());
     connect_rooms_with_doors(plan, outer_room, &hallway, door_id_generator)?;
 
-    add_rooms(plan, client, namespace, &hallway, door_id_generator, kind).await?;
+    let resources = listings.get(namespace, kind).to_vec();
+    add_rooms(plan, &resources, namespace, &hallway, door_id_generator)?;
     debug!("Finished setting up {kind} hallway and rooms");
-    Ok(())
+    Ok(resources)
 }
 
 fn create_hallway_room(namespace: &str, kind: &str) -> RoomData {
     RoomData {
-        id: format!("{namespace}-{kind}s"),
-        name: format!("{namespace} {kind}s Hallway"),
+        id: format!("{namespace}-{}", pluralize(kind)),
+        name: format!("{namespace} {} Hallway", pluralize(kind)),
+        ..Default::default()
     }
 }
 
-async fn add_rooms(
+fn add_rooms(
     plan: &mut FloorPlan,
-    client: &Client,
+    resources: &[IntegrationResource],
     namespace: &str,
     outer_room: &RoomData,
     door_id_generator: &mut usize,
-    kind: &str,
 ) -> FloorPlanResult<()> {
-    debug!("Adding {kind} rooms");
-    if let Ok(resources) = get_names(client, kind, namespace).await {
-        for r in resources {
-            let room = create_resource_room(namespace, &r);
-            plan.add_room(room.clone());
-            connect_rooms_with_doors(plan, &room, outer_room, door_id_generator)?;
-
-            if let Some(parent) = &r.parent {
-                connect_to_parent_room(plan, namespace, &room, parent, door_id_generator)?;
-            }
+    for r in resources {
+        let room = create_resource_room(namespace, r);
+        plan.add_room(room.clone());
+        connect_rooms_with_doors(plan, outer_room, &room, door_id_generator)?;
 
-            add_container_rooms(plan, namespace, &r, &room, door_id_generator)?;
+        if let Some(parent) = &r.parent {
+            connect_to_parent_room(plan, namespace, &room, parent, door_id_generator)?;
         }
-        debug!("Finished adding {kind} rooms");
+
+        add_container_rooms(plan, namespace, r, &room, door_id_generator)?;
     }
     Ok(())
 }
 
 fn create_resource_room(namespace: &str, r: &IntegrationResource) -> RoomData {
     RoomData {
-        id: format!("{namespace}-{}-{}", r.kind, r.name),
+        id: resource_room_id(namespace, &r.kind, &r.name),
         name: format!("{} {}", r.kind, r.name),
+        attributes: r.attributes.clone(),
+        health: r.health,
+        resource: Some(room_resource(namespace, r)),
     }
 }
 
@@ -141,11 +256,12 @@ fn connect_to_parent_room(
     parent: &IntegrationResource,
     door_id_generator: &mut usize,
 ) -> FloorPlanResult<()> {
-    let parent_room_id = format!("{namespace}-{}-{}", parent.kind, parent.name);
+    let parent_room_id = resource_room_id(namespace, &parent.kind, &parent.name);
     let cplan = plan.clone();
     let parent_room = cplan.get_room_by_id(&parent_room_id);
     if let Ok(parent_room) = parent_room {
-        connect_rooms_with_doors(plan, room, parent_room, door_id_generator)?;
+        // the way out is through the hallway, so the owner is only next door
+        connect_rooms_side_by_side(plan, parent_room, room, door_id_generator)?;
     } else {
         debug!("Owner room not found: {parent_room_id}");
     }
@@ -162,7 +278,7 @@ fn add_container_rooms(
     for container in r.children.clone() {
         let container_room = create_container_room(namespace, r, &container);
         plan.add_room(container_room.clone());
-        connect_rooms_with_doors(plan, &container_room, room, door_id_generator)?;
+        connect_rooms_with_doors(plan, room, &container_room, door_id_generator)?;
         add_volume_mount_rooms(
             plan,
             namespace,
@@ -181,8 +297,9 @@ fn create_container_room(
     container: &IntegrationResource,
 ) -> RoomData {
     RoomData {
-        id: format!("{namespace}-{}-{}-{}", r.kind, "container", container.name),
+        id: container_room_id(namespace, r, container),
         name: format!("{} {}", "container", container.name),
+        ..Default::default()
     }
 }
 
@@ -197,7 +314,7 @@ fn add_volume_mount_rooms(
     for volume_mount in container.children.clone() {
         let volume_mount_room = create_volume_mount_room(namespace, r, container, &volume_mount);
         plan.add_room(volume_mount_room.clone());
-        connect_rooms_with_doors(plan, &volume_mount_room, container_room, door_id_generator)?;
+        connect_rooms_with_doors(plan, container_room, &volume_mount_room, door_id_generator)?;
     }
     Ok(())
 }
@@ -209,41 +326,61 @@ fn create_volume_mount_room(
     volume_mount: &IntegrationResource,
 ) -> RoomData {
     RoomData {
-        id: format!(
-            "{namespace}-{}-{}-{}-{}",
-            r.kind, "container", container.name, volume_mount.name
-        ),
+        id: volume_mount_room_id(namespace, r, container, volume_mount),
         name: format!("{} {}", "volume mount", volume_mount.name),
+        ..Default::default()
     }
 }
 
-async fn generate() -> FloorPlanResult<FloorPlan> {
-    let client = create_k8s_client().await?;
-    let ns_list = fetch_namespaces(&client).await?;
+/**
+* a failure anywhere but an rbac refusal fails the whole floorplan, so a dropped connection never
+* replaces the world with a half empty one
+*/
+async fn generate(client: &Client, forbidden: &mut Vec<String>) -> FloorPlanResult<FloorPlan> {
+    let namespaces = fetch_namespaces(client, forbidden).await?;
+    let listings = fetch_listings(client, &namespaces).await?;
+    forbidden.extend(listings.forbidden.iter().cloned());
+
     let mut floorplan = FloorPlan::new();
     let cluster_room = create_cluster_room();
     floorplan.add_room(cluster_room.clone());
 
     let mut door_id = 0;
-    for ns in ns_list {
-        if let Some(namespace) = ns.metadata.name {
-            process_namespace(
-                &mut floorplan,
-                &cluster_room,
-                &mut door_id,
-                &client,
-                &namespace,
-            )
-            .await?;
-        }
+    let mut cluster_resources = Vec::new();
+    for kind in CLUSTER_SCOPED_KINDS {
+        cluster_resources.extend(setup_hallway_and_rooms(
+            &mut floorplan,
+            "cluster",
+            &cluster_room,
+            &mut door_id,
+            kind,
+            &listings,
+        )?);
+    }
+    connect_related_rooms(&mut floorplan, "cluster", &cluster_resources, &mut door_id)?;
+
+    for namespace in &namespaces {
+        process_namespace(
+            &mut floorplan,
+            &cluster_room,
+            &mut door_id,
+            &listings,
+            namespace,
+        )?;
     }
 
     Ok(floorplan)
 }
 
-async fn publish_floorplan(ctx: &mut TaskContext) -> FloorPlanResult<()> {
-    let floorplan = generate().await?;
+/**
+* returns what we were not allowed to see
+*/
+async fn publish_floorplan(ctx: &mut TaskContext) -> FloorPlanResult<Vec<String>> {
+    let client = create_k8s_client().await?;
+    let mut forbidden = Vec::new();
+    let floorplan = generate(&client, &mut forbidden).await?;
     ctx.run_on_main_thread(move |ctx| {
+        ctx.world.resource_mut::<K8sClient>().0 = Some(client);
         if let Some(mut events) = ctx.world.get_resource_mut::<Events<FloorPlanEvent>>() {
             events.send(FloorPlanEvent { floorplan });
             debug!("...Generated new floorplan");
@@ -252,18 +389,339 @@ async fn publish_floorplan(ctx: &mut TaskContext) -> FloorPlanResult<()> {
         }
     })
     .await;
-    Ok(())
+    Ok(forbidden)
+}
+
+async fn set_connection_status(ctx: &mut TaskContext, status: ConnectionStatus) {
+    ctx.run_on_main_thread(move |ctx| {
+        *ctx.world.resource_mut::<ConnectionStatus>() = status;
+    })
+    .await;
+}
+
+/**
+* doubles with every failed attempt in a row, up to a minute
+*/
+fn backoff_secs(attempt: u32) -> u64 {
+    2_u64
+        .saturating_pow(attempt.saturating_sub(1))
+        .saturating_mul(INITIAL_BACKOFF_SECS)
+        .min(MAX_BACKOFF_SECS)
 }
 
+/**
+* while the cluster can't be reached the last floorplan stays in play and we retry with backoff
+*/
 pub fn init_k8s_live_floorplan_publisher(runtime: ResMut<TokioTasksRuntime>) {
     runtime.spawn_background_task(|mut ctx| async move {
+        set_connection_status(&mut ctx, ConnectionStatus::Connecting).await;
+        let mut attempt = 0;
         loop {
-            if let Err(e) = publish_floorplan(&mut ctx).await {
-                panic!("No K8S FloorPlanEvent: {e:?}");
-            }
-            let generator_poll_secs = Cli::parse().generator_poll_secs.unwrap_or(60);
-            tokio::time::sleep(Duration::from_secs(generator_poll_secs.into())).await;
+            let wait_secs = match publish_floorplan(&mut ctx).await {
+                Ok(forbidden) => {
+                    attempt = 0;
+                    let status = if forbidden.is_empty() {
+                        ConnectionStatus::Connected
+                    } else {
+                        ConnectionStatus::Partial(forbidden)
+                    };
+                    set_connection_status(&mut ctx, status).await;
+                    Cli::parse().generator_poll_secs.unwrap_or(60).into()
+                }
+                Err(e) => {
+                    attempt += 1;
+                    let wait_secs = backoff_secs(attempt);
+                    warn!("No K8S floorplan, keeping the last one, retry {attempt} in {wait_secs}s: {e:?}");
+                    let status = ConnectionStatus::Retrying {
+                        attempt,
+                        wait_secs,
+                    };
+                    set_connection_status(&mut ctx, status).await;
+                    wait_secs
+                }
+            };
+            tokio::time::sleep(Duration::from_secs(wait_secs)).await;
             debug!("Generating new floorplan...");
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::k8s_live::k8s_mock::{MockApiServer, named, pod};
+    use serde_json::json;
+
+    fn connected_room_ids(floorplan: &FloorPlan, room_id: &str) -> Vec<String> {
+        floorplan
+            .get_doors_and_connected_rooms(room_id)
+            .expect("room expected")
+            .into_iter()
+            .map(|(_, room)| room.id.clone())
+            .collect()
+    }
+
+    fn default_namespace() -> MockApiServer {
+        MockApiServer::new()
+            .with_list(
+                "/api/v1/namespaces",
+                "NamespaceList",
+                vec![named("default")],
+            )
+            .with_list(
+                "/apis/apps/v1/namespaces/default/replicasets",
+                "ReplicaSetList",
+                vec![named("web")],
+            )
+            .with_list(
+                "/api/v1/namespaces/default/pods",
+                "PodList",
+                vec![pod("web-1", "web", &["app", "sidecar"])],
+            )
+    }
+
+    #[tokio::test]
+    async fn test_process_namespace() {
+        let (client, _) = default_namespace().start();
+        let mut floorplan = FloorPlan::new();
+        let cluster_room = create_cluster_room();
+        floorplan.add_room(cluster_room.clone());
+        let mut door_id = 0;
+        let listings = fetch_listings(&client, &["default".to_string()])
+            .await
+            .expect("listings expected");
+
+        process_namespace(
+            &mut floorplan,
+            &cluster_room,
+            &mut door_id,
+            &listings,
+            "default",
+        )
+        .expect("namespace expected");
+
+        assert!(connected_room_ids(&floorplan, "cluster").contains(&"default".to_string()));
+        assert!(connected_room_ids(&floorplan, "default").contains(&"default-Pods".to_string()));
+        let pod_doors = connected_room_ids(&floorplan, "default-Pod-web-1");
+        assert!(pod_doors.contains(&"default-Pods".to_string()));
+        assert!(pod_doors.contains(&"default-ReplicaSet-web".to_string()));
+        assert!(pod_doors.contains(&"default-Pod-web-1-container-app".to_string()));
+        assert!(pod_doors.contains(&"default-Pod-web-1-container-sidecar".to_string()));
+        let pod_exits: Vec<String> = floorplan
+            .get_doors_and_connected_rooms("default-Pod-web-1")
+            .expect("room expected")
+            .into_iter()
+            .filter(|(door, _)| door.is_exit)
+            .map(|(_, room)| room.id.clone())
+            .collect();
+        assert_eq!(pod_exits, vec!["default-Pods"]);
+        let pod_room = floorplan.get_room_by_id("default-Pod-web-1").unwrap();
+        assert_eq!(pod_room.health, crate::floorplan::RoomHealth::Pending);
+        assert!(listings.forbidden.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_namespace_connects_workload_owners() {
+        let (client, _) = MockApiServer::new()
+            .with_list(
+                "/apis/batch/v1/namespaces/default/cronjobs",
+                "CronJobList",
+                vec![named("nightly")],
+            )
+            .with_list(
+                "/apis/batch/v1/namespaces/default/jobs",
+                "JobList",
+                vec![json!({
+                    "metadata": {
+                        "name": "nightly-1",
+                        "ownerReferences": [{ "kind": "CronJob", "name": "nightly" }],
+                    },
+                })],
+            )
+            .with_list(
+                "/apis/apps/v1/namespaces/default/statefulsets",
+                "StatefulSetList",
+                vec![named("db")],
+            )
+            .with_list(
+                "/api/v1/namespaces/default/pods",
+                "PodList",
+                vec![json!({
+                    "metadata": {
+                        "name": "db-0",
+                        "ownerReferences": [{ "kind": "StatefulSet", "name": "db" }],
+                    },
+                    "spec": { "containers": [{ "name": "postgres" }] },
+                })],
+            )
+            .start();
+        let mut floorplan = FloorPlan::new();
+        let cluster_room = create_cluster_room();
+        floorplan.add_room(cluster_room.clone());
+        let mut door_id = 0;
+        let listings = fetch_listings(&client, &["default".to_string()])
+            .await
+            .expect("listings expected");
+
+        process_namespace(
+            &mut floorplan,
+            &cluster_room,
+            &mut door_id,
+            &listings,
+            "default",
+        )
+        .expect("namespace expected");
+
+        assert!(
+            connected_room_ids(&floorplan, "default-CronJob-nightly")
+                .contains(&"default-Job-nightly-1".to_string())
+        );
+        assert!(
+            connected_room_ids(&floorplan, "default-StatefulSet-db")
+                .contains(&"default-Pod-db-0".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_namespace_skips_failing_kinds() {
+        let (client, _) = default_namespace()
+            .with_error("/api/v1/namespaces/default/secrets", 403, "Forbidden")
+            .start();
+        let mut floorplan = FloorPlan::new();
+        let cluster_room = create_cluster_room();
+        floorplan.add_room(cluster_room.clone());
+        let mut door_id = 0;
+        let listings = fetch_listings(&client, &["default".to_string()])
+            .await
+            .expect("listings expected");
+
+        process_namespace(
+            &mut floorplan,
+            &cluster_room,
+            &mut door_id,
+            &listings,
+            "default",
+        )
+        .expect("namespace expected");
+
+        assert!(floorplan.get_room_by_id("default-Pod-web-1").is_ok());
+        assert_eq!(connected_room_ids(&floorplan, "default-Secrets").len(), 1);
+        assert_eq!(listings.forbidden, vec!["default/Secret"]);
+    }
+
+    #[tokio::test]
+    async fn test_generate() {
+        let (client, server) = default_namespace()
+            .with_list("/api/v1/nodes", "NodeList", vec![named("node-1")])
+            .with_list(
+                "/api/v1/namespaces/default/services",
+                "ServiceList",
+                vec![json!({
+                    "metadata": { "name": "web" },
+                    "spec": { "selector": { "app": "web" } },
+                })],
+            )
+            .start();
+
+        let mut forbidden = Vec::new();
+        let floorplan = generate(&client, &mut forbidden)
+            .await
+            .expect("floorplan expected");
+        drop(client);
+
+        let lobby_doors = connected_room_ids(&floorplan, "cluster");
+        assert!(lobby_doors.contains(&"default".to_string()));
+        assert!(lobby_doors.contains(&"cluster-Nodes".to_string()));
+        assert!(floorplan.get_room_by_id("cluster-Node-node-1").is_ok());
+        let service_doors = connected_room_ids(&floorplan, "default-Service-web");
+        assert!(service_doors.contains(&"default-Pod-web-1".to_string()));
+
+        let requested = server.await.unwrap();
+        assert!(
+            requested
+                .iter()
+                .any(|uri| uri.starts_with("/api/v1/namespaces?"))
+        );
+        assert!(
+            requested
+                .iter()
+                .any(|uri| uri.starts_with("/api/v1/nodes?"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_fails_without_namespaces() {
+        let (client, _) = MockApiServer::new()
+            .with_error("/api/v1/namespaces", 401, "Unauthorized")
+            .start();
+
+        let result = generate(&client, &mut Vec::new()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_default_namespace_when_namespaces_forbidden() {
+        let (client, _) = default_namespace()
+            .with_error("/api/v1/namespaces", 403, "Forbidden")
+            .start();
+
+        let mut forbidden = Vec::new();
+        let floorplan = generate(&client, &mut forbidden)
+            .await
+            .expect("floorplan expected");
+
+        assert_eq!(forbidden, vec!["cluster/Namespace"]);
+        assert!(connected_room_ids(&floorplan, "cluster").contains(&"default".to_string()));
+        assert!(floorplan.get_room_by_id("default-Pod-web-1").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_generate_fails_when_a_list_fails() {
+        let (client, _) = default_namespace()
+            .with_error("/api/v1/namespaces/default/pods", 503, "ServiceUnavailable")
+            .start();
+
+        let result = generate(&client, &mut Vec::new()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_listings_pages_every_namespace() {
+        let namespaces = vec!["default".to_string(), "kube-system".to_string()];
+        let (client, server) = MockApiServer::new()
+            .with_pages(
+                "/api/v1/namespaces/kube-system/pods",
+                "PodList",
+                vec![
+                    vec![pod("dns-1", "dns", &["dns"])],
+                    vec![pod("dns-2", "dns", &["dns"])],
+                ],
+            )
+            .start();
+
+        let listings = fetch_listings(&client, &namespaces)
+            .await
+            .expect("listings expected");
+        drop(client);
+
+        let pods: Vec<&str> = listings
+            .get("kube-system", "Pod")
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(pods, vec!["dns-1", "dns-2"]);
+        let requested = server.await.unwrap();
+        let expected = CLUSTER_SCOPED_KINDS.len() + namespaces.len() * NAMESPACED_KINDS.len() + 1;
+        assert_eq!(requested.len(), expected);
+        assert!(requested.iter().any(|uri| uri.contains("continue=1")));
+    }
+
+    #[test]
+    fn test_backoff_secs() {
+        let backoff: Vec<u64> = (1..=8).map(backoff_secs).collect();
+        assert_eq!(backoff, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(backoff_secs(1000), MAX_BACKOFF_SECS);
+    }
+}
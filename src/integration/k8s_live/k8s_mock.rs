@@ -0,0 +1,174 @@
+use http::{Request, Response};
+use kube::Client;
+use kube::client::Body;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use tokio::task::JoinHandle;
+use tower_test::mock;
+
+enum MockResponse {
+    List {
+        kind: String,
+        pages: Vec<Vec<Value>>,
+    },
+    Object(Value),
+    Text(String),
+    Error {
+        code: u16,
+        reason: String,
+    },
+}
+
+/**
+ * a stand-in for the kube api server - a tower mock service answers the `Client` with canned lists
+ * and objects so the live integration can be tested without a cluster - a route whose path ends in
+ * `?container=<name>` only answers requests for that container, ie: one container's log
+ */
+#[derive(Default)]
+pub struct MockApiServer {
+    routes: HashMap<String, MockResponse>,
+}
+
+impl MockApiServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// a list served in one go, ie: `/api/v1/namespaces/default/pods`
+    pub fn with_list(self, path: &str, kind: &str, items: Vec<Value>) -> Self {
+        self.with_pages(path, kind, vec![items])
+    }
+
+    /**
+     * a list served a page at a time to clients asking with a `limit`, continue tokens lead to the
+     * next page - clients without a limit get every page at once like from a real api server
+     */
+    pub fn with_pages(mut self, path: &str, kind: &str, pages: Vec<Vec<Value>>) -> Self {
+        let kind = kind.to_string();
+        self.routes
+            .insert(path.to_string(), MockResponse::List { kind, pages });
+        self
+    }
+
+    /// a single object, ie: the pod at `/api/v1/namespaces/default/pods/web`
+    pub fn with_object(mut self, path: &str, object: Value) -> Self {
+        self.routes
+            .insert(path.to_string(), MockResponse::Object(object));
+        self
+    }
+
+    /// plain text rather than json, ie: `/api/v1/namespaces/default/pods/web/log?container=app`
+    pub fn with_text(mut self, path: &str, text: &str) -> Self {
+        self.routes
+            .insert(path.to_string(), MockResponse::Text(text.to_string()));
+        self
+    }
+
+    /// a failure status for the path, ie: 403 `Forbidden` when rbac says no
+    pub fn with_error(mut self, path: &str, code: u16, reason: &str) -> Self {
+        let reason = reason.to_string();
+        self.routes
+            .insert(path.to_string(), MockResponse::Error { code, reason });
+        self
+    }
+
+    /**
+     * hands out a client talking to this server - the server runs until the client is dropped and
+     * then returns every uri it was asked for, in order
+     */
+    pub fn start(self) -> (Client, JoinHandle<Vec<String>>) {
+        let (service, mut handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let server = tokio::spawn(async move {
+            let mut requested = Vec::new();
+            while let Some((request, send)) = handle.next_request().await {
+                requested.push(request.uri().to_string());
+                let (status, body) = self.respond(request.uri().path(), request.uri().query());
+                send.send_response(
+                    Response::builder()
+                        .status(status)
+                        .body(Body::from(body.into_bytes()))
+                        .expect("valid response"),
+                );
+            }
+            requested
+        });
+        (Client::new(service, "default"), server)
+    }
+
+    fn respond(&self, path: &str, query: Option<&str>) -> (u16, String) {
+        let route = query_param(query, "container")
+            .and_then(|container| self.routes.get(&format!("{path}?container={container}")))
+            .or_else(|| self.routes.get(path));
+        match route {
+            Some(MockResponse::List { kind, pages }) => {
+                let paged = query_param(query, "limit").is_some();
+                let page = query_param(query, "continue")
+                    .and_then(|token| token.parse::<usize>().ok())
+                    .unwrap_or(0);
+                let (items, next): (Vec<Value>, Option<String>) = if paged {
+                    let next = (page + 1 < pages.len()).then(|| (page + 1).to_string());
+                    (pages.get(page).cloned().unwrap_or_default(), next)
+                } else {
+                    (pages.concat(), None)
+                };
+                (200, list(kind, items, next).to_string())
+            }
+            Some(MockResponse::Object(object)) => (200, object.to_string()),
+            Some(MockResponse::Text(text)) => (200, text.clone()),
+            Some(MockResponse::Error { code, reason }) => {
+                (*code, status(*code, reason, path).to_string())
+            }
+            None => (200, list("List", Vec::new(), None).to_string()),
+        }
+    }
+}
+
+fn query_param<'a>(query: Option<&'a str>, name: &str) -> Option<&'a str> {
+    query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn list(kind: &str, items: Vec<Value>, next: Option<String>) -> Value {
+    json!({
+        "apiVersion": "v1",
+        "kind": kind,
+        "metadata": { "continue": next, "resourceVersion": "1" },
+        "items": items,
+    })
+}
+
+fn status(code: u16, reason: &str, path: &str) -> Value {
+    json!({
+        "apiVersion": "v1",
+        "kind": "Status",
+        "status": "Failure",
+        "message": format!("{reason}: {path}"),
+        "reason": reason,
+        "code": code,
+    })
+}
+
+/// the smallest object the integration can make a room for
+pub fn named(name: &str) -> Value {
+    json!({ "metadata": { "name": name } })
+}
+
+/// a pod owned by a replicaset and labelled `app` after it, with the containers given
+pub fn pod(name: &str, replica_set: &str, containers: &[&str]) -> Value {
+    let containers: Vec<Value> = containers.iter().map(|c| json!({ "name": c })).collect();
+    json!({
+        "metadata": {
+            "name": name,
+            "creationTimestamp": "2025-01-18T14:30:26Z",
+            "labels": { "app": replica_set },
+            "ownerReferences": [{
+                "apiVersion": "apps/v1", "kind": "ReplicaSet", "name": replica_set, "uid": "1"
+            }],
+        },
+        "spec": { "containers": containers },
+        "status": { "phase": "Running" },
+    })
+}
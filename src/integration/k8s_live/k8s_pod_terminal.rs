@@ -0,0 +1,310 @@
+use super::k8s_integration_systems::K8sClient;
+use crate::floorplan::RoomResource;
+use crate::terminal::{Terminal, TerminalOpened};
+use bevy::prelude::*;
+use bevy_tokio_tasks::{TaskContext, TokioTasksRuntime};
+use futures::stream::{self, BoxStream};
+use futures::{AsyncBufReadExt, StreamExt};
+use k8s_openapi::api::core::v1::{Event, Pod};
+use k8s_openapi::jiff::Timestamp;
+use kube::{
+    Client,
+    api::{Api, ListParams, LogParams},
+};
+use std::error::Error;
+use tokio::task::JoinHandle;
+
+const LOG_TAIL_LINES: i64 = 50;
+
+/**
+* the background task streaming into the terminal, so it can be stopped when the terminal closes
+*/
+#[derive(Default, Resource)]
+pub struct PodTerminalTask {
+    handle: Option<JoinHandle<()>>,
+}
+
+fn format_event(event: &Event) -> String {
+    format!(
+        "{} {} (x{}): {}",
+        event.type_.as_deref().unwrap_or("Normal"),
+        event.reason.as_deref().unwrap_or_default(),
+        event.count.unwrap_or(1),
+        event.message.as_deref().unwrap_or_default()
+    )
+}
+
+/**
+* events recorded through the newer events api only have an event time
+*/
+fn last_seen(event: &Event) -> Option<Timestamp> {
+    event
+        .last_timestamp
+        .as_ref()
+        .map(|time| time.0)
+        .or_else(|| event.event_time.as_ref().map(|time| time.0))
+}
+
+/**
+* the events the cluster recorded about a pod, oldest first
+*/
+pub async fn get_pod_events(
+    client: &Client,
+    namespace: &str,
+    pod: &str,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let events: Api<Event> = Api::namespaced(client.clone(), namespace);
+    let lp = ListParams::default().fields(&format!(
+        "involvedObject.kind=Pod,involvedObject.name={pod}"
+    ));
+    let mut event_list = events
+        .list(&lp)
+        .await
+        .map_err(|e| format!("Failed to list events: {e}"))?
+        .items;
+    event_list.sort_by_key(last_seen);
+    Ok(event_list.iter().map(format_event).collect())
+}
+
+/**
+* the last lines of every container's log followed by whatever they write from now on, each line
+* prefixed with the container it came from - a container whose log can't be read says so in a line
+* of its own and the others carry on
+*/
+pub async fn stream_container_logs(
+    client: &Client,
+    namespace: &str,
+    pod: &str,
+) -> Result<BoxStream<'static, String>, Box<dyn Error>> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let containers: Vec<String> = pods
+        .get(pod)
+        .await
+        .map_err(|e| format!("Failed to get pod: {e}"))?
+        .spec
+        .map(|spec| spec.containers.into_iter().map(|c| c.name).collect())
+        .unwrap_or_default();
+
+    let mut streams = Vec::new();
+    for container in containers {
+        let lp = LogParams {
+            container: Some(container.clone()),
+            tail_lines: Some(LOG_TAIL_LINES),
+            follow: true,
+            ..Default::default()
+        };
+        match pods.log_stream(pod, &lp).await {
+            Ok(lines) => streams.push(
+                lines
+                    .lines()
+                    .map(move |line| match line {
+                        Ok(line) => format!("[{container}] {line}"),
+                        Err(e) => format!("[{container}] log stream failed: {e}"),
+                    })
+                    .boxed(),
+            ),
+            Err(e) => streams.push(
+                stream::once(async move { format!("[{container}] Failed to stream logs: {e}") })
+                    .boxed(),
+            ),
+        }
+    }
+    Ok(stream::select_all(streams).boxed())
+}
+
+/**
+* events first, then the logs as they come in - when either can't be read that is a line in the
+* terminal and the other is still shown
+*/
+pub async fn stream_pod_terminal_lines(
+    client: &Client,
+    namespace: &str,
+    pod: &str,
+) -> BoxStream<'static, String> {
+    let mut lines = vec!["-- events --".to_string()];
+    match get_pod_events(client, namespace, pod).await {
+        Ok(events) => lines.extend(events),
+        Err(e) => lines.push(e.to_string()),
+    }
+    lines.push("-- logs --".to_string());
+    match stream_container_logs(client, namespace, pod).await {
+        Ok(logs) => stream::iter(lines).chain(logs).boxed(),
+        Err(e) => {
+            lines.push(e.to_string());
+            stream::iter(lines).boxed()
+        }
+    }
+}
+
+async fn show_line(ctx: &mut TaskContext, resource: &RoomResource, line: String) {
+    let resource = resource.clone();
+    ctx.run_on_main_thread(move |ctx| {
+        ctx.world
+            .resource_mut::<Terminal>()
+            .push_for(&resource, line);
+    })
+    .await;
+}
+
+/**
+* the pod is streamed with the publisher's client, so until the cluster has been reached there is
+* only a line saying so
+*/
+async fn feed_terminal(
+    ctx: &mut TaskContext,
+    client: Option<Client>,
+    resource: &RoomResource,
+    namespace: &str,
+) {
+    let Some(client) = client else {
+        show_line(
+            ctx,
+            resource,
+            "Not connected to the cluster yet".to_string(),
+        )
+        .await;
+        return;
+    };
+    let mut lines = stream_pod_terminal_lines(&client, namespace, &resource.name).await;
+    while let Some(line) = lines.next().await {
+        show_line(ctx, resource, line).await;
+    }
+}
+
+/**
+* start streaming when the terminal opens in a pod room, stop when it closes or opens somewhere else
+*/
+pub fn stream_pod_terminal(
+    runtime: Res<TokioTasksRuntime>,
+    terminal: Res<Terminal>,
+    client: Res<K8sClient>,
+    mut task: ResMut<PodTerminalTask>,
+    mut opened: EventReader<TerminalOpened>,
+) {
+    if !terminal.is_open()
+        && let Some(handle) = task.handle.take()
+    {
+        handle.abort();
+    }
+    for TerminalOpened { resource } in opened.read() {
+        if let Some(handle) = task.handle.take() {
+            handle.abort();
+        }
+        let (Some(namespace), "Pod") = (resource.namespace.clone(), resource.kind.as_str()) else {
+            continue;
+        };
+        let resource = resource.clone();
+        let client = client.0.clone();
+        task.handle = Some(runtime.spawn_background_task(|mut ctx| async move {
+            feed_terminal(&mut ctx, client, &resource, &namespace).await;
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::k8s_live::k8s_mock::{MockApiServer, pod};
+    use serde_json::json;
+
+    async fn terminal_lines(client: &Client, pod: &str) -> Vec<String> {
+        stream_pod_terminal_lines(client, "default", pod)
+            .await
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_stream_pod_terminal_lines() {
+        let (client, server) = MockApiServer::new()
+            .with_list(
+                "/api/v1/namespaces/default/events",
+                "EventList",
+                vec![
+                    json!({
+                        "metadata": { "name": "web.2" }, "involvedObject": { "name": "web" },
+                        "type": "Warning", "reason": "BackOff", "count": 3,
+                        "message": "Back-off restarting failed container",
+                        "lastTimestamp": "2025-01-18T14:35:00Z",
+                    }),
+                    json!({
+                        "metadata": { "name": "web.1" }, "involvedObject": { "name": "web" },
+                        "reason": "Pulled", "message": "Container image pulled",
+                        "lastTimestamp": "2025-01-18T14:30:00Z",
+                    }),
+                ],
+            )
+            .with_object(
+                "/api/v1/namespaces/default/pods/web",
+                pod("web", "web", &["app"]),
+            )
+            .with_text(
+                "/api/v1/namespaces/default/pods/web/log",
+                "starting\nlistening on :8080\n",
+            )
+            .start();
+
+        let lines = terminal_lines(&client, "web").await;
+        drop(client);
+        let requested = server.await.unwrap();
+
+        assert!(requested[0].contains("involvedObject.kind%3DPod"));
+        assert!(requested[0].contains("involvedObject.name%3Dweb"));
+        assert_eq!(
+            lines,
+            vec![
+                "-- events --",
+                "Normal Pulled (x1): Container image pulled",
+                "Warning BackOff (x3): Back-off restarting failed container",
+                "-- logs --",
+                "[app] starting",
+                "[app] listening on :8080",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_pod_terminal_lines_missing_pod() {
+        let (client, _) = MockApiServer::new()
+            .with_error("/api/v1/namespaces/default/pods/gone", 404, "NotFound")
+            .start();
+
+        let lines = terminal_lines(&client, "gone").await;
+
+        assert_eq!(lines[..2], ["-- events --", "-- logs --"]);
+        assert!(lines[2].starts_with("Failed to get pod"));
+        assert!(lines[2].contains("NotFound"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_pod_terminal_lines_failing_container_log() {
+        let (client, _) = MockApiServer::new()
+            .with_error("/api/v1/namespaces/default/events", 500, "InternalError")
+            .with_object(
+                "/api/v1/namespaces/default/pods/web",
+                pod("web", "web", &["init", "app"]),
+            )
+            .with_error(
+                "/api/v1/namespaces/default/pods/web/log?container=init",
+                400,
+                "BadRequest",
+            )
+            .with_text(
+                "/api/v1/namespaces/default/pods/web/log?container=app",
+                "ready\n",
+            )
+            .start();
+
+        let lines = terminal_lines(&client, "web").await;
+
+        assert_eq!(lines[0], "-- events --");
+        assert!(lines[1].starts_with("Failed to list events"));
+        assert_eq!(lines[2], "-- logs --");
+        assert!(
+            lines[3..]
+                .iter()
+                .any(|line| line.starts_with("[init] Failed to stream logs"))
+        );
+        assert!(lines[3..].contains(&"[app] ready".to_string()));
+    }
+}
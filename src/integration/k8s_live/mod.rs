@@ -1,6 +1,9 @@
 pub mod k8s_api;
 pub mod k8s_integration_plugin;
 pub mod k8s_integration_systems;
+#[cfg(test)]
+pub mod k8s_mock;
+pub mod k8s_pod_terminal;
 // pub mod k8s_utils;
 //
 pub use k8s_integration_plugin::K8sIntegrationPlugin;
@@ -20,6 +20,7 @@ fn create_rooms(lim: u8) -> Vec<RoomData> {
         .map(|i| RoomData {
             id: i.to_string(),
             name: format!("Room {i}"),
+            ..Default::default()
         })
         .collect()
 }
@@ -108,7 +109,7 @@ pub fn fire_room25_floorplan_event(mut events: EventWriter<FloorPlanEvent>) {
 }
 
 // create a 25 room floorplan
-fn generate_room25_floorplan() -> FloorPlanResult<FloorPlan> {
+pub fn generate_room25_floorplan() -> FloorPlanResult<FloorPlan> {
     info!("generating 25RoomData FloorPlanEvent");
     let mut floorplan = FloorPlan::new();
 
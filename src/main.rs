@@ -5,7 +5,9 @@ use bevy_tokio_tasks::TokioTasksPlugin;
 use camera::CameraPlugin;
 use clap::Parser;
 use constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use controls::ControlsPlugin;
 use door::DoorPlugin;
+use hud::HudPlugin;
 use integration::integration_plugin::IntegrationPlugin;
 use pause::PausePlugin;
 #[cfg(feature = "perfmon")]
@@ -13,20 +15,26 @@ use perf::PerfPlugin;
 use player::PlayerPlugin;
 use room::RoomPlugin;
 use schedule::SchedulePlugin;
+use settings::SettingsPlugin;
 use state::StatePlugin;
+use terminal::TerminalPlugin;
 
 mod camera;
 mod cli;
 mod constants;
+mod controls;
 mod door;
 mod floorplan;
+mod hud;
 mod integration;
 mod pause;
 mod perf;
 mod player;
 mod room;
 mod schedule;
+mod settings;
 mod state;
+mod terminal;
 
 fn main() {
     cli::Cli::parse();
@@ -37,14 +45,20 @@ fn main() {
             EmbeddedAssetPlugin {
                 mode: PluginMode::ReplaceDefault,
             },
-            DefaultPlugins.set(WindowPlugin {
-                primary_window: Some(Window {
-                    title: "Doors".to_string(),
-                    resolution: bevy::window::WindowResolution::from((WINDOW_WIDTH, WINDOW_HEIGHT)),
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: "Doors".to_string(),
+                        resolution: bevy::window::WindowResolution::from((
+                            WINDOW_WIDTH,
+                            WINDOW_HEIGHT,
+                        )),
+                        ..default()
+                    }),
                     ..default()
-                }),
-                ..default()
-            }),
+                })
+                // pixel art stays crisp scaled up
+                .set(ImagePlugin::default_nearest()),
             SchedulePlugin,
             PhysicsPlugins::default(),
             CameraPlugin,
@@ -53,9 +67,12 @@ fn main() {
             DoorPlugin,
             StatePlugin,
             PausePlugin,
+            TerminalPlugin,
+            HudPlugin,
             IntegrationPlugin,
             #[cfg(feature = "perfmon")]
             PerfPlugin,
         ))
+        .add_plugins((ControlsPlugin, SettingsPlugin))
         .run();
 }
@@ -1,7 +1,9 @@
+use crate::controls::Action;
 use crate::state::GameState;
 use bevy::color::Color;
 use bevy::prelude::*;
 use bevy::text::TextBounds;
+use leafwing_input_manager::prelude::ActionState;
 
 use super::pause_component::PausedText;
 
@@ -16,22 +18,28 @@ pub fn remove_pause_text(mut commands: Commands, query: Query<Entity, With<Pause
 pub fn handle_pause_events(
     mut next_state: ResMut<NextState<GameState>>,
     state: Res<State<GameState>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    action_query: Query<&ActionState<Action>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        match state.get() {
-            GameState::InGame => next_state.set(GameState::Paused),
-            GameState::Paused => next_state.set(GameState::InGame),
-            _ => (), //noop
+    // the controls screen has the keys to itself
+    if *state == GameState::Controls {
+        return;
+    }
+    for action_state in action_query.iter() {
+        if action_state.just_pressed(&Action::Pause) {
+            match state.get() {
+                GameState::InGame => next_state.set(GameState::Paused),
+                GameState::Paused => next_state.set(GameState::InGame),
+                _ => (), //noop
+            }
+        } else if action_state.just_pressed(&Action::Quit) {
+            // exit the game
+            std::process::exit(0);
         }
-    } else if keyboard_input.just_pressed(KeyCode::KeyQ) {
-        // exit the game
-        std::process::exit(0);
     }
 }
 
 pub fn pause_game(mut time: ResMut<Time<Virtual>>, state: Res<State<GameState>>) {
-    if *state == GameState::Paused {
+    if matches!(state.get(), GameState::Paused | GameState::Controls) {
         time.set_relative_speed(0.0); // Freeze physics and animation
     } else {
         time.set_relative_speed(1.0); // Resume physics
@@ -1,3 +1,4 @@
+pub mod player_character;
 pub mod player_component;
 pub mod player_plugin;
 pub mod player_systems;
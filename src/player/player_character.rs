@@ -0,0 +1,247 @@
+use crate::cli::PlayerType;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// the animations every character has, or stands in for with one it does have
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayerClip {
+    #[default]
+    Idle,
+    Run,
+    Jump,
+    Fall,
+    Land,
+    DoorIn,  // walking into a doorway
+    DoorOut, // stepping out of one
+}
+
+/// a run of frames in a sprite sheet
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Clip {
+    pub first: usize,
+    pub frames: usize,
+    pub fps: f32,
+    pub looping: bool,
+}
+
+impl Clip {
+    const fn looping(first: usize, frames: usize, fps: f32) -> Self {
+        Self {
+            first,
+            frames,
+            fps,
+            looping: true,
+        }
+    }
+
+    const fn once(first: usize, frames: usize, fps: f32) -> Self {
+        Self {
+            first,
+            frames,
+            fps,
+            looping: false,
+        }
+    }
+}
+
+pub enum CharacterArt {
+    Aseprite {
+        path: &'static str,
+        tag: fn(PlayerClip) -> &'static str,
+    },
+    Sheet {
+        path: &'static str,
+        cell: UVec2,
+        columns: u32,
+        rows: u32,
+        size: Vec2,     // drawn about as big as the aseprite demo character
+        anchor: Anchor, // the middle of the character rather than of its cell
+        clip: fn(PlayerClip) -> Clip,
+    },
+}
+
+/// the art faces right, it is flipped for running left
+pub fn character(player_type: PlayerType) -> CharacterArt {
+    match player_type {
+        PlayerType::Demo1 => CharacterArt::Aseprite {
+            path: "Player_bevy_aseprite_ultra_demo.aseprite",
+            tag: demo_tag,
+        },
+        PlayerType::Player1 => CharacterArt::Sheet {
+            path: "PlayerSheet.png",
+            cell: UVec2::new(96, 84),
+            columns: 14,
+            rows: 48,
+            size: Vec2::new(57.6, 50.4),
+            anchor: Anchor::Custom(Vec2::new(0.0, -0.256)),
+            clip: player_sheet_clip,
+        },
+        PlayerType::Gabe => CharacterArt::Sheet {
+            path: "gabe-idle-run.png",
+            cell: UVec2::splat(24),
+            columns: 7,
+            rows: 1,
+            size: Vec2::splat(24.0),
+            anchor: Anchor::Center,
+            clip: gabe_clip,
+        },
+        PlayerType::Stickman => CharacterArt::Sheet {
+            path: "stickman.png",
+            cell: UVec2::splat(16),
+            columns: 6,
+            rows: 3,
+            size: Vec2::splat(24.0),
+            anchor: Anchor::Center,
+            clip: stickman_clip,
+        },
+    }
+}
+
+/// the demo has no jumping or falling, only walking in four directions
+const fn demo_tag(clip: PlayerClip) -> &'static str {
+    match clip {
+        PlayerClip::Idle | PlayerClip::Land | PlayerClip::Jump | PlayerClip::Fall => "idle",
+        PlayerClip::Run => "walk-right",
+        PlayerClip::DoorIn => "walk-up",
+        PlayerClip::DoorOut => "walk-down",
+    }
+}
+
+/// a row of the sheet for each animation
+const fn player_sheet_clip(clip: PlayerClip) -> Clip {
+    const ROW: usize = 14;
+    match clip {
+        PlayerClip::Idle | PlayerClip::DoorOut => Clip::looping(0, 7, 8.0),
+        PlayerClip::Run => Clip::looping(2 * ROW, 7, 12.0),
+        PlayerClip::DoorIn => Clip::looping(ROW, 8, 10.0),
+        PlayerClip::Jump => Clip::once(5 * ROW, 1, 10.0),
+        PlayerClip::Fall => Clip::once(7 * ROW, 1, 10.0),
+        PlayerClip::Land => Clip::once(8 * ROW, 2, 10.0),
+    }
+}
+
+/// standing, then six running frames that stand in for the rest
+const fn gabe_clip(clip: PlayerClip) -> Clip {
+    match clip {
+        PlayerClip::Idle | PlayerClip::DoorOut => Clip::looping(0, 1, 1.0),
+        PlayerClip::Run | PlayerClip::DoorIn => Clip::looping(1, 6, 12.0),
+        PlayerClip::Jump => Clip::once(3, 1, 10.0),
+        PlayerClip::Fall => Clip::once(5, 1, 10.0),
+        PlayerClip::Land => Clip::once(1, 1, 10.0),
+    }
+}
+
+/// idle and run rows, then rise, top of the jump and fall
+const fn stickman_clip(clip: PlayerClip) -> Clip {
+    match clip {
+        PlayerClip::Idle | PlayerClip::DoorOut => Clip::looping(0, 4, 6.0),
+        PlayerClip::Run | PlayerClip::DoorIn => Clip::looping(6, 4, 10.0),
+        PlayerClip::Jump => Clip::once(12, 1, 10.0),
+        PlayerClip::Fall => Clip::once(14, 1, 10.0),
+        PlayerClip::Land => Clip::once(13, 1, 10.0),
+    }
+}
+
+/// the opaque pixels in a frame of the image, none while the frame is empty or the image unreadable
+pub fn opaque_bounds(image: &Image, frame: URect) -> Option<URect> {
+    let mut bounds: Option<URect> = None;
+    for y in frame.min.y..frame.max.y {
+        for x in frame.min.x..frame.max.x {
+            if image.get_color_at(x, y).ok()?.alpha() > 0.0 {
+                let pixel = URect::new(x, y, x + 1, y + 1);
+                bounds = Some(bounds.map_or(pixel, |b| b.union(pixel)));
+            }
+        }
+    }
+    bounds
+}
+
+/**
+ * the opaque part of a frame in the player's own units, centered sideways so it still fits when the
+ * sprite is flipped - `size` is how big the whole frame is drawn and `anchor` where the player's
+ * origin is on it
+ */
+pub fn body_rect(frame: URect, opaque: URect, size: Vec2, anchor: Vec2) -> Rect {
+    let to_local = |pixel: UVec2| {
+        let fraction = (pixel - frame.min).as_vec2() / frame.size().as_vec2();
+        Vec2::new(fraction.x - 0.5 - anchor.x, 0.5 - fraction.y - anchor.y) * size
+    };
+    let (top_left, bottom_right) = (to_local(opaque.min), to_local(opaque.max));
+    let half_width = (bottom_right.x - top_left.x) / 2.0;
+    Rect::new(-half_width, bottom_right.y, half_width, top_left.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIPS: [PlayerClip; 7] = [
+        PlayerClip::Idle,
+        PlayerClip::Run,
+        PlayerClip::Jump,
+        PlayerClip::Fall,
+        PlayerClip::Land,
+        PlayerClip::DoorIn,
+        PlayerClip::DoorOut,
+    ];
+
+    #[test]
+    fn test_body_fits_the_opaque_pixels() {
+        use bevy::render::render_asset::RenderAssetUsages;
+        use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+        // two 8x8 frames side by side, a 2x4 figure standing at the bottom of the second
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: 16,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        for y in 4..8 {
+            for x in 11..13 {
+                image
+                    .set_color_at(x, y, Color::WHITE)
+                    .expect("pixel expected");
+            }
+        }
+        let first = URect::new(0, 0, 8, 8);
+        let second = URect::new(8, 0, 16, 8);
+
+        assert_eq!(opaque_bounds(&image, first), None);
+        let opaque = opaque_bounds(&image, second).expect("bounds expected");
+        assert_eq!(opaque, URect::new(11, 4, 13, 8));
+
+        // drawn at twice the size around the center of the frame
+        let body = body_rect(second, opaque, Vec2::splat(16.0), Vec2::ZERO);
+        assert_eq!(body, Rect::new(-2.0, -8.0, 2.0, 0.0));
+
+        // with the origin at the bottom of the frame
+        let body = body_rect(second, opaque, Vec2::splat(16.0), Vec2::new(0.0, -0.5));
+        assert_eq!(body, Rect::new(-2.0, 0.0, 2.0, 8.0));
+    }
+
+    #[test]
+    fn test_clips_stay_on_their_sheet() {
+        for player_type in [PlayerType::Player1, PlayerType::Gabe, PlayerType::Stickman] {
+            let CharacterArt::Sheet {
+                columns,
+                rows,
+                clip,
+                ..
+            } = character(player_type)
+            else {
+                panic!("sprite sheet expected");
+            };
+            for player_clip in CLIPS {
+                let clip = clip(player_clip);
+                assert!(clip.frames > 0);
+                assert!(clip.first + clip.frames <= (columns * rows) as usize);
+            }
+        }
+    }
+}
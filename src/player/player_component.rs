@@ -1,65 +1,50 @@
-use avian2d::{parry::shape::SharedShape, prelude::*};
+use avian2d::prelude::*;
 use bevy::prelude::*;
-use leafwing_input_manager::Actionlike;
 
-// Define movement constants
-const PLAYER_MOVE_SPEED: f32 = 500.0; // Horizontal movement speed
-pub const PLAYER_JUMP_FORCE: f32 = 25000.0; // Jump force applied when pressing space
-pub const PLAYER_GRAVITY_SCALE: f32 = 25.0; // Gravity multiplier for falling speed
+use super::player_character::{Clip, PlayerClip};
+use crate::settings::PlayerSettings;
+
+pub const FEET_HEIGHT: f32 = 1.0; // in the player's own units, before their scale
+pub const FEET_WIDTH: f32 = 0.8; // of the body, so walls don't count as ground
 
 #[derive(Bundle)]
 pub struct PlayerBundle {
-    pub rigid_body: RigidBody,
-    pub collider: Collider,
-    pub external_force: ExternalForce,
+    pub rigid_body: RigidBody, // the collider follows once the character's art has loaded
+    pub locked_axes: LockedAxes,
     pub gravity: GravityScale,
     pub mass: Mass,
     pub friction: Friction,
     pub player: Player,
     pub movable: Movable,
     pub grounded: Grounded,
+    pub jump_timer: JumpTimer,
 }
 
 impl PlayerBundle {
-    pub fn new(// texture: Handle<Image>,
-    ) -> Self {
+    pub fn new(settings: &PlayerSettings) -> Self {
         Self {
             rigid_body: RigidBody::Dynamic,
-            collider: Collider::from(SharedShape::cuboid(15.0, 15.0)), // todo: base on image
-            external_force: ExternalForce::default(),
-            gravity: GravityScale(PLAYER_GRAVITY_SCALE),
+            locked_axes: LockedAxes::ROTATION_LOCKED, // the body isn't centered on the player, keep them upright
+            gravity: GravityScale(settings.gravity_scale),
             mass: Mass(1.0),
-            friction: Friction {
-                dynamic_coefficient: 0.3,
-                static_coefficient: 0.5,
-                combine_rule: CoefficientCombine::Average,
-            },
-            player: Player::default(),
+            friction: settings.friction.friction(),
+            player: Player::new(),
             movable: Movable,
             grounded: Grounded(false),
+            jump_timer: JumpTimer::default(),
         }
     }
 }
 
-#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
-pub enum Action {
-    MoveLeft,
-    MoveRight,
-    Jump,
-    Enter,
-}
-
 #[derive(Component)]
 pub struct Player {
-    pub walk_speed: f32,
     pub state: PlayerState,
     pub direction: PlayerDirection,
 }
 
 impl Player {
-    const fn default() -> Self {
+    const fn new() -> Self {
         Self {
-            walk_speed: PLAYER_MOVE_SPEED,
             state: PlayerState::Stand,
             direction: PlayerDirection::Right,
         }
@@ -72,17 +57,102 @@ pub struct Grounded(pub bool);
 #[derive(Component)]
 pub struct Movable;
 
-#[derive(Debug, PartialEq, Eq)]
+/// how long since the player last stood on something and since they last pressed jump
+#[derive(Component)]
+pub struct JumpTimer {
+    pub since_grounded: f32,
+    pub since_jump_pressed: f32,
+    pub rising: bool, // on the way up from a jump with jump still held
+}
+
+impl Default for JumpTimer {
+    fn default() -> Self {
+        Self {
+            since_grounded: f32::INFINITY,
+            since_jump_pressed: f32::INFINITY,
+            rising: false,
+        }
+    }
+}
+
+impl JumpTimer {
+    /// whether the player jumps this frame - each press jumps at most once
+    pub fn tick(
+        &mut self,
+        grounded: bool,
+        jump_pressed: bool,
+        delta_secs: f32,
+        settings: &PlayerSettings,
+    ) -> bool {
+        self.since_grounded = if grounded {
+            0.0
+        } else {
+            self.since_grounded + delta_secs
+        };
+        self.since_jump_pressed = if jump_pressed {
+            0.0
+        } else {
+            self.since_jump_pressed + delta_secs
+        };
+        let jump = self.since_grounded <= settings.coyote_time
+            && self.since_jump_pressed <= settings.jump_buffer;
+        if jump {
+            *self = Self {
+                rising: true,
+                ..Self::default()
+            };
+        }
+        jump
+    }
+
+    /// whether the jump is cut short this frame - it is, once, when jump is let go on the way up
+    pub const fn cut(&mut self, jump_held: bool, rising: bool) -> bool {
+        let cut = self.rising && rising && !jump_held;
+        self.rising &= rising && jump_held;
+        cut
+    }
+}
+
+/// a thin sensor under the player, what they stand on touches it
+#[derive(Component)]
+pub struct Feet;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum PlayerState {
     Walk,
     Stand,
-    Jump,
+    Jump, // on the way up
     Fall,
+    Land, // the moment the player touches down
 }
+/// the way the player faces
 #[derive(Debug, PartialEq, Eq)]
 pub enum PlayerDirection {
-    Up,
-    Down,
     Left,
     Right,
 }
+
+/// the clip playing and how far into it the player is
+#[derive(Component, Default)]
+pub struct PlayerAnimation {
+    pub clip: PlayerClip,
+    pub frame: usize,
+    pub elapsed: f32,
+    pub finished: bool, // only clips that play once finish
+}
+
+impl PlayerAnimation {
+    pub fn play(&mut self, clip: PlayerClip) {
+        if self.clip != clip {
+            *self = Self { clip, ..default() };
+        }
+    }
+}
+
+/// the aseprite tag for each clip
+#[derive(Component)]
+pub struct AsepriteTags(pub fn(PlayerClip) -> &'static str);
+
+/// the sprite sheet frames for each clip
+#[derive(Component)]
+pub struct SheetClips(pub fn(PlayerClip) -> Clip);
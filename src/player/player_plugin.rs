@@ -1,14 +1,12 @@
-use crate::{schedule::InGameSet, state::GameState};
+use crate::{controls::Action, schedule::InGameSet, state::GameState};
 use bevy::prelude::*;
 use bevy_aseprite_ultra::AsepriteUltraPlugin;
 use leafwing_input_manager::plugin::InputManagerPlugin;
 
-use super::{
-    player_component::Action,
-    player_systems::{
-        check_grounded, detect_player_at_door, player_animation, player_enters_new_room,
-        player_movement, spawn_player,
-    },
+use super::player_systems::{
+    check_grounded, detect_player_at_door, fit_player_collider, play_aseprite_clip,
+    play_sheet_clip, player_animation, player_enters_new_room, player_movement, reset_door_walk,
+    spawn_player, stop_player, walk_into_door, walk_out_of_door,
 };
 
 pub struct PlayerPlugin;
@@ -22,12 +20,31 @@ impl Plugin for PlayerPlugin {
                     .in_set(InGameSet::Update)
                     .run_if(in_state(GameState::InGame)),
             )
+            .add_systems(
+                Update,
+                (play_aseprite_clip, play_sheet_clip).in_set(InGameSet::Render),
+            )
+            .add_systems(Update, fit_player_collider.in_set(InGameSet::Update))
             .add_systems(
                 Update,
                 player_enters_new_room
                     .in_set(InGameSet::Update)
                     .run_if(in_state(GameState::RoomChange)),
             )
+            .add_systems(OnEnter(GameState::EnteringDoor), stop_player)
+            .add_systems(
+                Update,
+                walk_into_door
+                    .in_set(InGameSet::Update)
+                    .run_if(in_state(GameState::EnteringDoor)),
+            )
+            .add_systems(OnEnter(GameState::LeavingDoor), reset_door_walk)
+            .add_systems(
+                Update,
+                walk_out_of_door
+                    .in_set(InGameSet::Update)
+                    .run_if(in_state(GameState::LeavingDoor)),
+            )
             .add_systems(
                 Update,
                 player_movement
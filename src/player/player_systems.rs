@@ -1,23 +1,29 @@
 use super::Player;
+use super::player_character::{CharacterArt, PlayerClip, body_rect, character, opaque_bounds};
 use super::player_component::{
-    Action, Movable, PLAYER_JUMP_FORCE, PlayerBundle, PlayerDirection, PlayerState,
+    AsepriteTags, FEET_HEIGHT, FEET_WIDTH, Feet, JumpTimer, Movable, PlayerAnimation, PlayerBundle,
+    PlayerDirection, PlayerState, SheetClips,
 };
-use crate::constants::PLAYER_LAYER;
+use crate::cli::Cli;
+use crate::constants::{DOOR_WALK_DURATION, PLAYER_LAYER};
+use crate::controls::{Action, Bindings};
 use crate::door::Door;
+use crate::door::DoorWalk;
 use crate::door::Platform;
+use crate::door::door_component::{PLATFORM_HEIGHT, PLATFORM_WIDTH};
 use crate::player::player_component::Grounded;
 use crate::room::Floor;
 use crate::room::room_component::{CurrentFloorPlan, RoomState};
+use crate::settings::Settings;
 use crate::state::GameState;
 use crate::state::state_component::FadeEffect;
+use crate::state::state_system::transition_speed;
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_aseprite_ultra::prelude::*;
 use bevy_lit::prelude::LightOccluder2d;
-use leafwing_input_manager::{
-    InputManagerBundle,
-    prelude::{ActionState, InputMap},
-};
+use clap::Parser;
+use leafwing_input_manager::{InputManagerBundle, prelude::ActionState};
 
 pub fn player_enters_new_room(
     mut commands: Commands,
@@ -38,9 +44,10 @@ pub fn player_enters_new_room(
                         y: 4.0,
                         z: 2.0,
                     })
+                    // in the doorway, ready to step out once the room fades in
                     .with_translation(Vec3::new(
                         new_location.x,
-                        new_location.y + 50.0,
+                        new_location.y + PLATFORM_HEIGHT / 2.0 + PLATFORM_WIDTH / 4.0,
                         2.0,
                     )),
                 );
@@ -53,108 +60,245 @@ pub fn spawn_player(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    bindings: Res<Bindings>,
+    settings: Res<Settings>,
 ) {
-    let input_map = InputMap::new([
-        (Action::Jump, KeyCode::Space),
-        (Action::MoveLeft, KeyCode::ArrowLeft),
-        (Action::MoveRight, KeyCode::ArrowRight),
-        (Action::Enter, KeyCode::ArrowUp),
-    ]);
+    let input_map = bindings.input_map();
 
     let player_shape = meshes.add(Rectangle::new(8.0, 16.0));
 
-    commands.spawn((
+    let mut player = commands.spawn((
         Mesh2d(player_shape),
         LightOccluder2d::default(),
-        AseSpriteAnimation {
-            animation: Animation::tag("walk-up")
-                .with_repeat(AnimationRepeat::Loop)
-                .with_direction(AnimationDirection::Forward)
-                .with_speed(2.0),
-            aseprite: asset_server.load("Player_bevy_aseprite_ultra_demo.aseprite"),
-        },
         Transform::default()
             .with_scale(Vec3::new(4.0, 4.0, 1.0))
             .with_translation(Vec3::new(0.0, 0.0, PLAYER_LAYER)), //needed for player to be in front of the door
         InputManagerBundle::with_map(input_map),
-        PlayerBundle::new(),
+        PlayerBundle::new(&settings.player),
+        PlayerAnimation::default(),
     ));
+    match character(Cli::parse().player.unwrap_or_default()) {
+        CharacterArt::Aseprite { path, tag } => {
+            player.insert((
+                AseSpriteAnimation {
+                    animation: Animation::tag(tag(PlayerClip::Idle))
+                        .with_repeat(AnimationRepeat::Loop)
+                        .with_direction(AnimationDirection::Forward)
+                        .with_speed(2.0),
+                    aseprite: asset_server.load(path),
+                },
+                AsepriteTags(tag),
+            ));
+        }
+        CharacterArt::Sheet {
+            path,
+            cell,
+            columns,
+            rows,
+            size,
+            anchor,
+            clip,
+        } => {
+            let layout = layouts.add(TextureAtlasLayout::from_grid(
+                cell, columns, rows, None, None,
+            ));
+            player.insert((
+                Sprite {
+                    image: asset_server.load(path),
+                    texture_atlas: Some(TextureAtlas {
+                        layout,
+                        index: clip(PlayerClip::Idle).first,
+                    }),
+                    custom_size: Some(size),
+                    anchor,
+                    ..default()
+                },
+                SheetClips(clip),
+            ));
+        }
+    }
 }
 
-pub fn player_animation(mut animation_query: Query<(&mut AseSpriteAnimation, &Player)>) {
-    for (mut ase_sprite_animation, player) in &mut animation_query {
-        match player.state {
-            PlayerState::Walk => match player.direction {
-                PlayerDirection::Up => {
-                    ase_sprite_animation.animation.play_loop("walk-up");
-                }
-                PlayerDirection::Down => {
-                    ase_sprite_animation.animation.play_loop("walk-down");
-                }
-                PlayerDirection::Left => {
-                    ase_sprite_animation.animation.play_loop("walk-right"); // there is no walk-left animation
-                }
-                PlayerDirection::Right => {
-                    ase_sprite_animation.animation.play_loop("walk-right");
-                }
+/**
+ * the player's body is the opaque part of the frame showing once the character's art has loaded,
+ * with their feet a thin sensor along the bottom of it.  until an aseprite loads the sprite shows
+ * bevy's 1x1 placeholder image, which is no shape to fit a body to.
+ */
+#[allow(clippy::type_complexity)]
+pub fn fit_player_collider(
+    mut commands: Commands,
+    query: Query<(Entity, &Sprite, Option<&AseSpriteAnimation>), (With<Player>, Without<Collider>)>,
+    images: Res<Assets<Image>>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    aseprites: Res<Assets<Aseprite>>,
+) {
+    for (entity, sprite, animation) in &query {
+        let art_loaded = animation.is_none_or(|animation| aseprites.contains(&animation.aseprite));
+        if sprite.image == Handle::default() || !art_loaded {
+            continue;
+        }
+        let Some(image) = images.get(&sprite.image) else {
+            continue;
+        };
+        let frame = match &sprite.texture_atlas {
+            Some(atlas) => match layouts.get(&atlas.layout) {
+                Some(layout) => layout.textures[atlas.index],
+                None => continue,
             },
-            _ => {
-                ase_sprite_animation.animation.play_loop("idle");
+            None => URect::from_corners(UVec2::ZERO, image.size()),
+        };
+        let Some(opaque) = opaque_bounds(image, frame) else {
+            continue;
+        };
+        let size = sprite.custom_size.unwrap_or_else(|| frame.size().as_vec2());
+        let body = body_rect(frame, opaque, size, sprite.anchor.as_vec());
+
+        commands
+            .entity(entity)
+            .insert(Collider::compound(vec![(
+                body.center(),
+                0.0,
+                Collider::rectangle(body.width(), body.height()),
+            )]))
+            .with_children(|builder| {
+                builder.spawn((
+                    Collider::rectangle(body.width() * FEET_WIDTH, FEET_HEIGHT),
+                    Sensor,
+                    CollidingEntities::default(),
+                    Transform::from_xyz(body.center().x, body.min.y, 0.0),
+                    Feet,
+                ));
+            });
+    }
+}
+
+/**
+ * the clip for what the player is doing, facing the way they last ran - a landing plays out unless
+ * the player runs or jumps off again
+ */
+pub fn player_animation(mut query: Query<(&Player, &mut PlayerAnimation, &mut Sprite)>) {
+    for (player, mut animation, mut sprite) in &mut query {
+        sprite.flip_x = player.direction == PlayerDirection::Left;
+        let clip = match player.state {
+            PlayerState::Walk => PlayerClip::Run,
+            PlayerState::Jump => PlayerClip::Jump,
+            PlayerState::Fall => PlayerClip::Fall,
+            PlayerState::Land => PlayerClip::Land,
+            PlayerState::Stand if animation.clip == PlayerClip::Land && !animation.finished => {
+                PlayerClip::Land
             }
+            PlayerState::Stand => PlayerClip::Idle,
+        };
+        animation.play(clip);
+    }
+}
+
+pub fn play_aseprite_clip(
+    mut query: Query<(&PlayerAnimation, &AsepriteTags, &mut AseSpriteAnimation)>,
+) {
+    for (animation, tags, mut ase_sprite_animation) in &mut query {
+        ase_sprite_animation
+            .animation
+            .play_loop(tags.0(animation.clip));
+    }
+}
+
+/// steps through the sheet's frames for the clip, holding the last frame of one that plays once
+pub fn play_sheet_clip(
+    mut query: Query<(&mut PlayerAnimation, &SheetClips, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (mut animation, clips, mut sprite) in &mut query {
+        let clip = clips.0(animation.clip);
+        animation.elapsed += time.delta_secs();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let frames_elapsed = (animation.elapsed * clip.fps) as usize;
+        let frame = if clip.looping {
+            frames_elapsed % clip.frames
+        } else {
+            frames_elapsed.min(clip.frames - 1)
+        };
+        animation.frame = frame;
+        animation.finished = !clip.looping && frames_elapsed >= clip.frames;
+        if let Some(atlas) = sprite.texture_atlas.as_mut() {
+            atlas.index = clip.first + frame;
         }
     }
 }
 
-#[allow(clippy::useless_let_if_seq)]
+/**
+ * a jump is an impulse, straight to its takeoff speed, and letting go of jump on the way up cuts it
+ * short - running speeds up toward the top speed, and slows to a stop without a move key, more
+ * slowly in the air than on the ground.  a jump pressed a moment before landing still counts, and
+ * if jump was let go of before touching down it is cut on the frame it takes off, a hop.
+ */
+#[allow(clippy::type_complexity)]
 pub fn player_movement(
     mut query: Query<
         (
-            &mut ExternalForce,
             &Grounded,
+            &mut JumpTimer,
+            &mut LinearVelocity,
             &ActionState<Action>,
             &mut Player,
         ),
         With<Player>,
     >,
+    settings: Res<Settings>,
+    time: Res<Time>,
 ) {
-    if let Ok((mut force, grounded, action_state, mut player)) = query.get_single_mut() {
-        force.set_force(Vec2::ZERO);
-
-        let mut pressed = false;
-
-        if grounded.0 && action_state.pressed(&Action::Jump) {
-            force.apply_force(Vec2::new(0.0, PLAYER_JUMP_FORCE));
-            player.direction = PlayerDirection::Up;
-            player.state = PlayerState::Stand;
-            pressed = true;
+    if let Ok((grounded, mut jump_timer, mut velocity, action_state, mut player)) =
+        query.get_single_mut()
+    {
+        let settings = &settings.player;
+        let delta_secs = time.delta_secs();
+
+        if jump_timer.tick(
+            grounded.0,
+            action_state.just_pressed(&Action::Jump),
+            delta_secs,
+            settings,
+        ) {
+            velocity.y = settings.jump_speed;
         }
-
-        if action_state.pressed(&Action::Jump) {
-            player.direction = PlayerDirection::Up;
-            player.state = PlayerState::Jump;
-            pressed = true;
-        } else if !grounded.0 {
-            player.direction = PlayerDirection::Down;
-            player.state = PlayerState::Fall;
-            pressed = true;
+        if jump_timer.cut(action_state.pressed(&Action::Jump), velocity.y > 0.0) {
+            velocity.y *= settings.jump_cut;
         }
 
-        if action_state.pressed(&Action::MoveLeft) {
-            force.apply_force(Vec2::new(-player.walk_speed, 0.0));
+        let left = action_state.pressed(&Action::MoveLeft);
+        let right = action_state.pressed(&Action::MoveRight);
+        if left {
             player.direction = PlayerDirection::Left;
-            player.state = PlayerState::Walk;
-            pressed = true;
         }
-        if action_state.pressed(&Action::MoveRight) {
-            force.apply_force(Vec2::new(player.walk_speed, 0.0));
+        if right {
             player.direction = PlayerDirection::Right;
-            player.state = PlayerState::Walk;
-            pressed = true;
-        }
-
-        if !pressed {
-            player.state = PlayerState::Stand;
         }
+        let running = left || right;
+        let heading = f32::from(u8::from(right)) - f32::from(u8::from(left));
+        let acceleration = if grounded.0 {
+            settings.ground_acceleration
+        } else {
+            settings.air_acceleration
+        };
+        let step = acceleration * delta_secs;
+        let speed = velocity.x
+            + heading
+                .mul_add(settings.max_speed, -velocity.x)
+                .clamp(-step, step);
+        velocity.x = speed.clamp(-settings.max_speed, settings.max_speed);
+
+        player.state = if !grounded.0 && velocity.y > 0.0 {
+            PlayerState::Jump
+        } else if !grounded.0 {
+            PlayerState::Fall
+        } else if running {
+            PlayerState::Walk
+        } else if matches!(player.state, PlayerState::Jump | PlayerState::Fall) {
+            PlayerState::Land
+        } else {
+            PlayerState::Stand
+        };
     }
 }
 
@@ -165,7 +309,7 @@ pub fn detect_player_at_door(
     platform_query: Query<&Transform, With<Platform>>,
     action_state_query: Query<&ActionState<Action>>,
     mut current_floorplan: ResMut<CurrentFloorPlan>,
-    mut fade: ResMut<FadeEffect>,
+    mut door_walk: ResMut<DoorWalk>,
 ) {
     if let Ok(player_transform) = player_query.get_single() {
         for (door_transform, parent, door) in door_query.iter() {
@@ -180,8 +324,11 @@ pub fn detect_player_at_door(
                             current_floorplan.you_were_here =
                                 current_floorplan.you_are_here.clone();
                             current_floorplan.you_are_here = Some(door.room_id.clone());
-                            next_state.set(GameState::TransitioningOut);
-                            fade.fading_out = true;
+                            *door_walk = DoorWalk {
+                                room_id: Some(door.room_id.clone()),
+                                elapsed: 0.0,
+                            };
+                            next_state.set(GameState::EnteringDoor);
                         }
                     }
                 }
@@ -190,44 +337,102 @@ pub fn detect_player_at_door(
     }
 }
 
-#[allow(clippy::type_complexity)]
-pub fn check_grounded(
-    mut collision_events: EventReader<Collision>,
-    mut query: Query<(Entity, &mut Grounded, &Transform), With<Movable>>,
-    ground_query: Query<(Entity, &Transform), (With<Floor>, Without<Movable>)>, // Query for ground entities
-    platform_query: Query<(Entity, &Transform), (With<Platform>, Without<Movable>)>, // Query for platforms
+/// no drifting off while the door is opening
+pub fn stop_player(mut query: Query<&mut LinearVelocity, With<Player>>) {
+    for mut velocity in &mut query {
+        velocity.0 = Vec2::ZERO;
+    }
+}
+
+/**
+ * the player steps into the open doorway and into the dark behind it, then the room fades out
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn walk_into_door(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut fade: ResMut<FadeEffect>,
+    mut door_walk: ResMut<DoorWalk>,
+    mut player_query: Query<(&mut Transform, &mut Sprite, &mut PlayerAnimation), With<Player>>,
+    door_query: Query<(&Transform, &Parent, &Door), Without<Player>>,
+    platform_query: Query<&Transform, (With<Platform>, Without<Player>)>,
+    settings: Res<Settings>,
+    action_query: Query<&ActionState<Action>>,
+    time: Res<Time>,
 ) {
-    let player_entities: Vec<Entity> = query.iter().map(|(entity, _, _)| entity).collect();
+    door_walk.elapsed += time.delta_secs() * transition_speed(&settings, &action_query);
+    let progress = (door_walk.elapsed / DOOR_WALK_DURATION).min(1.0);
+
+    let door_x = door_query
+        .iter()
+        .find(|(_, _, door)| door_walk.room_id.as_ref() == Some(&door.room_id))
+        .and_then(|(door_transform, parent, _)| {
+            let platform_transform = platform_query.get(parent.get()).ok()?;
+            Some(platform_transform.translation.x + door_transform.translation.x)
+        });
+    if let Ok((mut transform, mut sprite, mut animation)) = player_query.get_single_mut() {
+        if let Some(door_x) = door_x {
+            transform.translation.x = transform.translation.x.lerp(door_x, progress);
+        }
+        sprite.color.set_alpha(1.0 - progress);
+        animation.play(PlayerClip::DoorIn);
+    }
 
-    for (_, mut grounded, player_transform) in &mut query {
-        grounded.0 = false; // Reset grounded state each frame
+    if progress >= 1.0 {
+        next_state.set(GameState::TransitioningOut);
+        fade.fading_out = true;
+    }
+}
 
-        for collision in collision_events.read() {
-            let contacts = &collision.0;
+/**
+ * the player comes out of the door they went through, unless the room changed under them without
+ * a door, ie: the floorplan was refreshed
+ */
+pub fn walk_out_of_door(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut door_walk: ResMut<DoorWalk>,
+    mut player_query: Query<(&mut Sprite, &mut PlayerAnimation), With<Player>>,
+    settings: Res<Settings>,
+    action_query: Query<&ActionState<Action>>,
+    time: Res<Time>,
+) {
+    door_walk.elapsed += time.delta_secs() * transition_speed(&settings, &action_query);
+    let progress = if door_walk.room_id.is_some() {
+        (door_walk.elapsed / DOOR_WALK_DURATION).min(1.0)
+    } else {
+        1.0
+    };
+    if let Ok((mut sprite, mut animation)) = player_query.get_single_mut() {
+        sprite.color.set_alpha(progress);
+        animation.play(PlayerClip::DoorOut);
+    }
 
-            if contacts.is_sensor {
-                continue;
-            }
+    if progress >= 1.0 {
+        *door_walk = DoorWalk::default();
+        next_state.set(GameState::InGame);
+    }
+}
 
-            let involved_entities = [contacts.entity1, contacts.entity2];
-            if !involved_entities
-                .iter()
-                .any(|e| player_entities.contains(e))
-            {
-                continue;
-            }
+/// the walk out starts once the new room has faded in
+pub fn reset_door_walk(mut door_walk: ResMut<DoorWalk>) {
+    door_walk.elapsed = 0.0;
+}
 
-            for entity in &involved_entities {
-                if let Ok((_, ground_transform)) = ground_query.get(*entity) {
-                    if player_transform.translation.y > ground_transform.translation.y {
-                        grounded.0 = true;
-                    }
-                } else if let Ok((_, platform_transform)) = platform_query.get(*entity)
-                    && player_transform.translation.y > platform_transform.translation.y
-                {
-                    grounded.0 = true;
-                }
-            }
+/**
+ * a movable is grounded while its feet touch the floor or a platform - the feet are narrower than
+ * the body so a wall alongside never touches them
+ */
+#[allow(clippy::type_complexity)]
+pub fn check_grounded(
+    mut movable_query: Query<(Entity, &mut Grounded), With<Movable>>,
+    feet_query: Query<(&Parent, &CollidingEntities), With<Feet>>,
+    ground_query: Query<(), Or<(With<Floor>, With<Platform>)>>,
+) {
+    for (entity, mut grounded) in &mut movable_query {
+        let on_ground = feet_query.iter().any(|(parent, colliding)| {
+            parent.get() == entity && colliding.iter().any(|e| ground_query.contains(*e))
+        });
+        if grounded.0 != on_ground {
+            grounded.0 = on_ground;
         }
     }
 }
@@ -235,80 +440,226 @@ pub fn check_grounded(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::room::room_component::LeftWall;
+    use crate::settings::PlayerSettings;
+    use bevy::asset::RenderAssetUsages;
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 
-    #[test]
-    fn test_player_gets_grounded_on_collision() {
+    fn app() -> App {
         let mut app = App::new();
-
-        // Add the necessary components, plugins, and systems
         app.add_plugins(MinimalPlugins) // Use minimal set to speed up tests
-            .add_event::<Collision>() // Register the Collision event
-            .add_systems(Update, check_grounded); // Add our system
+            .add_systems(Update, check_grounded);
+        app
+    }
+
+    /// a movable whose feet touch the given entities
+    fn spawn_movable(app: &mut App, touching: &[Entity]) -> Entity {
+        let colliding = CollidingEntities(touching.iter().copied().collect());
+        app.world_mut()
+            .spawn((Movable, Grounded(false), Transform::from_xyz(0.0, 1.0, 0.0)))
+            .with_children(|builder| {
+                builder.spawn((Feet, colliding));
+            })
+            .id()
+    }
+
+    fn grounded(app: &App, movable: Entity) -> bool {
+        app.world().get::<Grounded>(movable).unwrap().0
+    }
+
+    #[test]
+    fn test_player_gets_grounded_on_collision() {
+        let mut app = app();
+        let floor = app.world_mut().spawn(Floor).id();
+        let player = spawn_movable(&mut app, &[floor]);
 
-        // Spawn a movable player entity
-        let player_entity = app
-            .world_mut()
-            .spawn((
-                Movable,
-                Grounded(false),
-                Transform::from_xyz(0.0, 1.0, 0.0), // Positioned above the ground
-            ))
-            .id();
-
-        // Spawn a ground entity
-        let ground_entity = app
-            .world_mut()
-            .spawn((
-                Floor,
-                Transform::from_xyz(0.0, 0.0, 0.0), // Ground is below the player
-            ))
-            .id();
-
-        // Send a collision event where the player lands on the ground
-
-        app.world_mut().send_event(Collision(Contacts {
-            entity1: player_entity,
-            entity2: ground_entity,
-            body_entity1: None, // No body entities involved for testing
-            body_entity2: None,
-            manifolds: vec![], // Empty manifolds for simplicity in the test
-            is_sensor: false,
-            during_current_frame: true, // Simulate contact occurring in the current frame
-            during_previous_frame: false,
-            total_normal_impulse: 0.0,
-            total_tangent_impulse: 0.0, // Or `Vector2::ZERO` for 3D, depending on enabled features
-        }));
-
-        // Run the app once to process the event
         app.update();
 
-        // Check if the player is now grounded
-        let grounded_component = app.world().get::<Grounded>(player_entity).unwrap();
         assert!(
-            grounded_component.0,
-            "Player should be grounded after collision."
+            grounded(&app, player),
+            "Player should be grounded on the floor."
         );
     }
 
     #[test]
     fn test_player_remains_ungrounded_without_collision() {
-        let mut app = App::new();
+        let mut app = app();
+        let player = spawn_movable(&mut app, &[]);
+
+        app.update();
+
+        assert!(
+            !grounded(&app, player),
+            "Player should remain ungrounded without collision."
+        );
+    }
+
+    #[test]
+    fn test_player_grounded_on_platform_not_wall() {
+        let mut app = app();
+        let platform = app.world_mut().spawn(Platform {}).id();
+        let wall = app.world_mut().spawn(LeftWall).id();
+        let on_platform = spawn_movable(&mut app, &[platform]);
+        let against_wall = spawn_movable(&mut app, &[wall]);
+
+        app.update();
+
+        assert!(grounded(&app, on_platform));
+        assert!(!grounded(&app, against_wall), "walls aren't ground");
+    }
+
+    #[test]
+    fn test_each_movable_grounded_by_its_own_feet() {
+        let mut app = app();
+        let floor = app.world_mut().spawn(Floor).id();
+        let platform = app.world_mut().spawn(Platform {}).id();
+        let movables = [
+            spawn_movable(&mut app, &[floor]),
+            spawn_movable(&mut app, &[]),
+            spawn_movable(&mut app, &[platform]),
+        ];
+
+        app.update();
+
+        assert_eq!(
+            movables.map(|movable| grounded(&app, movable)),
+            [true, false, true]
+        );
+
+        // the first jumps off the floor
+        let mut feet = app.world_mut().query::<(&Parent, &mut CollidingEntities)>();
+        for (parent, mut colliding) in feet.iter_mut(app.world_mut()) {
+            if parent.get() == movables[0] {
+                colliding.clear();
+            }
+        }
+        app.update();
+
+        assert_eq!(
+            movables.map(|movable| grounded(&app, movable)),
+            [false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_coyote_time() {
+        let settings = PlayerSettings::default();
+        let mut timer = JumpTimer::default();
+        assert!(!timer.tick(true, false, 0.016, &settings));
+
+        // ran off the edge and pressed jump just after
+        assert!(!timer.tick(false, false, settings.coyote_time / 2.0, &settings));
+        assert!(timer.tick(false, true, 0.016, &settings));
+
+        // too late
+        let mut timer = JumpTimer::default();
+        timer.tick(true, false, 0.016, &settings);
+        timer.tick(false, false, settings.coyote_time * 2.0, &settings);
+        assert!(!timer.tick(false, true, 0.016, &settings));
+    }
+
+    #[test]
+    fn test_jump_buffering() {
+        let settings = PlayerSettings::default();
+        let mut timer = JumpTimer::default();
+
+        // pressed jump just before landing
+        assert!(!timer.tick(false, true, 0.016, &settings));
+        assert!(timer.tick(true, false, settings.jump_buffer / 2.0, &settings));
+
+        // one press is one jump
+        assert!(!timer.tick(true, false, 0.016, &settings));
+
+        // pressed too long before landing
+        let mut timer = JumpTimer::default();
+        assert!(!timer.tick(false, true, 0.016, &settings));
+        assert!(!timer.tick(true, false, settings.jump_buffer * 2.0, &settings));
+    }
 
+    fn collider_app() -> App {
+        let mut app = App::new();
         app.add_plugins(MinimalPlugins)
-            .add_event::<Collision>()
-            .add_systems(Update, check_grounded);
+            .init_resource::<Assets<Image>>()
+            .init_resource::<Assets<TextureAtlasLayout>>()
+            .init_resource::<Assets<Aseprite>>()
+            .add_systems(Update, fit_player_collider);
+        // bevy's placeholder, the white pixel a sprite shows before its art is in
+        app.world_mut()
+            .resource_mut::<Assets<Image>>()
+            .insert(&Handle::default(), Image::default());
+        app
+    }
 
-        let player_entity = app
+    fn spawn_player(app: &mut App, image: Handle<Image>) -> Entity {
+        let player = Player {
+            state: PlayerState::Stand,
+            direction: PlayerDirection::Right,
+        };
+        app.world_mut()
+            .spawn((player, Sprite::from_image(image)))
+            .id()
+    }
+
+    #[test]
+    fn test_no_collider_fitted_to_the_placeholder_image() {
+        let mut app = collider_app();
+        let player = spawn_player(&mut app, Handle::default());
+
+        app.update();
+
+        assert!(app.world().get::<Collider>(player).is_none());
+    }
+
+    #[test]
+    fn test_collider_fitted_once_the_art_is_in() {
+        let mut app = collider_app();
+        let art = app
             .world_mut()
-            .spawn((Movable, Grounded(false), Transform::from_xyz(0.0, 1.0, 0.0)))
-            .id();
+            .resource_mut::<Assets<Image>>()
+            .add(Image::new_fill(
+                Extent3d {
+                    width: 16,
+                    height: 32,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                &[255, 255, 255, 255],
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::default(),
+            ));
+        let player = spawn_player(&mut app, art);
 
         app.update();
 
-        let grounded_component = app.world().get::<Grounded>(player_entity).unwrap();
-        assert!(
-            !grounded_component.0,
-            "Player should remain ungrounded without collision."
-        );
+        assert!(app.world().get::<Collider>(player).is_some());
+    }
+
+    #[test]
+    fn test_jump_cut_short() {
+        let settings = PlayerSettings::default();
+        let mut timer = JumpTimer::default();
+
+        // held all the way up
+        timer.tick(true, true, 0.016, &settings);
+        assert!(!timer.cut(true, true));
+        assert!(!timer.cut(true, false));
+        assert!(!timer.cut(false, false));
+
+        // let go on the way up, once
+        let mut timer = JumpTimer::default();
+        timer.tick(true, true, 0.016, &settings);
+        assert!(!timer.cut(true, true));
+        assert!(timer.cut(false, true));
+        assert!(!timer.cut(false, true));
+
+        // buffered in the air and let go before landing, a hop cut as it takes off
+        let mut timer = JumpTimer::default();
+        assert!(!timer.tick(false, true, 0.016, &settings));
+        assert!(timer.tick(true, false, 0.016, &settings));
+        assert!(timer.cut(false, true));
+
+        // rising without a jump, off a bounce
+        let mut timer = JumpTimer::default();
+        assert!(!timer.cut(false, true));
     }
 }
@@ -1,5 +1,8 @@
 pub mod room_component;
+pub mod room_door_order;
+pub mod room_layout;
 pub mod room_plugin;
+pub mod room_reachability;
 pub mod room_systems;
 
 pub use room_component::CurrentFloorPlan;
@@ -1,8 +1,8 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
 use bevy::prelude::*;
 
-use crate::floorplan::FloorPlan;
+use crate::floorplan::{FloorPlan, RoomHealth, RoomResource};
 
 #[derive(Component)]
 pub struct Floor;
@@ -27,8 +27,8 @@ pub struct DoorState {
     pub room_id: String,
     pub room_name: String,
     pub position: Vec2,
-    #[allow(dead_code)]
-    pub is_exit: bool,
+    pub is_exit: bool,      // the way back toward the lobby
+    pub health: RoomHealth, // of the room on the other side of the door
 }
 
 #[derive(Resource, Clone)]
@@ -36,8 +36,10 @@ pub struct RoomState {
     pub wall_distance_from_center: f32,
     pub floor_ceiling_width: f32,
     pub boundary_thickness: f32,
-    pub bounce_effect: f32,
     pub doors: Vec<DoorState>,
+    pub attributes: BTreeMap<String, String>,
+    pub health: RoomHealth,
+    pub resource: Option<RoomResource>,
     pub room_id: Option<String>,
     pub previous_room_id: Option<String>,
 }
@@ -51,8 +53,10 @@ impl Default for RoomState {
             wall_distance_from_center: DEFAULT_WALL_DISTANCE_FROM_CENTER,
             floor_ceiling_width: DEFAULT_WALL_DISTANCE_FROM_CENTER * 2.0,
             boundary_thickness: 0.1,
-            bounce_effect: 0.4,
             doors: vec![],
+            attributes: BTreeMap::new(),
+            health: RoomHealth::Unknown,
+            resource: None,
             room_id: None,
             previous_room_id: None,
         }
@@ -0,0 +1,147 @@
+use crate::cli::DoorOrder;
+use crate::floorplan::{DoorData, RoomData, RoomHealth};
+use std::cmp::Ordering;
+
+/// trouble first, so it's the first thing you see walking in
+const fn health_rank(health: RoomHealth) -> u8 {
+    match health {
+        RoomHealth::Failing => 0,
+        RoomHealth::Pending => 1,
+        RoomHealth::Unknown => 2,
+        RoomHealth::Ready => 3,
+    }
+}
+
+fn compare(order: DoorOrder, a: &RoomData, b: &RoomData) -> Ordering {
+    let kind = |room: &RoomData| room.resource.as_ref().map(|r| r.kind.clone());
+    // rooms without a creation time go last
+    let created = |room: &RoomData| {
+        let created = room.resource.as_ref().and_then(|r| r.created.clone());
+        (created.is_none(), created)
+    };
+    let ordering = match order {
+        DoorOrder::Alphabetical => Ordering::Equal,
+        DoorOrder::Kind => kind(a).cmp(&kind(b)),
+        DoorOrder::Health => health_rank(a.health).cmp(&health_rank(b.health)),
+        DoorOrder::Created => created(a).cmp(&created(b)),
+    };
+    ordering
+        .then_with(|| a.name.cmp(&b.name))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+/**
+ * sort the doors of a room the way the player asked, the names and ids of the rooms behind them
+ * settling ties so the order never depends on the order the api listed things in.  `previous` is
+ * the rooms behind the doors last time this room was laid out - if they are the same rooms they
+ * keep their places, even if ie: their health changed
+ */
+pub fn order_doors(
+    doors_and_rooms: &mut [(&DoorData, &RoomData)],
+    order: DoorOrder,
+    previous: &[String],
+) {
+    let unchanged = previous.len() == doors_and_rooms.len()
+        && doors_and_rooms
+            .iter()
+            .all(|(_, room)| previous.contains(&room.id));
+    if unchanged {
+        doors_and_rooms.sort_by_key(|(_, room)| previous.iter().position(|id| *id == room.id));
+    } else {
+        doors_and_rooms.sort_by(|(_, a), (_, b)| compare(order, a, b));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::floorplan::RoomResource;
+
+    fn room(name: &str, kind: &str, health: RoomHealth, created: Option<&str>) -> RoomData {
+        RoomData {
+            id: format!("{kind}-{name}"),
+            name: name.to_string(),
+            health,
+            resource: Some(RoomResource {
+                kind: kind.to_string(),
+                name: name.to_string(),
+                created: created.map(String::from),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn rooms() -> Vec<RoomData> {
+        vec![
+            room(
+                "web",
+                "Service",
+                RoomHealth::Ready,
+                Some("2025-01-18T14:31:33Z"),
+            ),
+            room("db", "Pod", RoomHealth::Pending, None),
+            room(
+                "api",
+                "Pod",
+                RoomHealth::Failing,
+                Some("2025-01-19T09:00:00Z"),
+            ),
+            room(
+                "cache",
+                "Service",
+                RoomHealth::Unknown,
+                Some("2025-01-18T14:30:26Z"),
+            ),
+        ]
+    }
+
+    fn ordered(order: DoorOrder, previous: &[String]) -> Vec<String> {
+        let door = DoorData::default();
+        let rooms = rooms();
+        let mut doors_and_rooms: Vec<(&DoorData, &RoomData)> =
+            rooms.iter().map(|room| (&door, room)).collect();
+        order_doors(&mut doors_and_rooms, order, previous);
+        doors_and_rooms
+            .iter()
+            .map(|(_, room)| room.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_order_doors() {
+        assert_eq!(
+            ordered(DoorOrder::Alphabetical, &[]),
+            vec!["api", "cache", "db", "web"]
+        );
+        assert_eq!(
+            ordered(DoorOrder::Kind, &[]),
+            vec!["api", "db", "cache", "web"]
+        );
+        assert_eq!(
+            ordered(DoorOrder::Health, &[]),
+            vec!["api", "db", "cache", "web"]
+        );
+        assert_eq!(
+            ordered(DoorOrder::Created, &[]),
+            vec!["cache", "web", "api", "db"]
+        );
+    }
+
+    #[test]
+    fn test_order_doors_keeps_previous_places() {
+        let previous: Vec<String> = ["Service-web", "Pod-db", "Service-cache", "Pod-api"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            ordered(DoorOrder::Alphabetical, &previous),
+            vec!["web", "db", "cache", "api"]
+        );
+
+        // a door came or went, so everything is sorted again
+        assert_eq!(
+            ordered(DoorOrder::Alphabetical, &previous[1..]),
+            vec!["api", "cache", "db", "web"]
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::cli::RoomLayout;
+use crate::door::door_component::{PLATFORM_HEIGHT, PLATFORM_WIDTH};
+use crate::settings::{PlayerSettings, Settings};
+use bevy::prelude::*;
+
+const ROW_HEIGHTS: &[f32] = &[1.0, 0.55, 0.85, 0.65, 0.45, 0.9, 0.5, 0.75, 0.95, 0.6]; // of a tier
+const LEDGE_SEPARATOR: f32 = PLATFORM_WIDTH * 1.5; // enough of a gap not to hit your head on the next ledge
+const DOOR_HEADROOM: f32 = 150.0; // the door and its sign stand on the top platform
+const JUMP_MARGIN: f32 = 0.8; // nobody should need a perfect jump to reach a door
+pub const PHYSICS_STEP: f32 = 1.0 / 64.0; // avian runs in bevy's fixed schedule
+const GRAVITY: f32 = 9.81; // avian's default, one pixel to the metre
+
+/// where the platforms of a room go, x from the middle of the room
+pub struct Layout {
+    pub room_width: f32,
+    pub positions: Vec<Vec2>,
+}
+
+pub fn gravity(player: &PlayerSettings) -> f32 {
+    GRAVITY * player.gravity_scale
+}
+
+/// how high the player's feet get above where they jumped from, with jump held all the way up
+pub fn jump_height(player: &PlayerSettings) -> f32 {
+    player.jump_speed.powi(2) / (2.0 * gravity(player))
+}
+
+/// how far the player gets sideways by the top of a jump, from a standstill
+pub fn jump_distance(player: &PlayerSettings) -> f32 {
+    let time_to_top = player.jump_speed / gravity(player);
+    let speeding_up = (player.max_speed / player.air_acceleration).min(time_to_top);
+    let speeding_up_distance = 0.5 * player.air_acceleration * speeding_up.powi(2);
+    player
+        .max_speed
+        .mul_add(time_to_top - speeding_up, speeding_up_distance)
+}
+
+/**
+ * the room height split into as few tiers as the jump allows, returns how many and how far apart
+ */
+fn tiers(floor_y: f32, ceiling_y: f32, player: &PlayerSettings) -> (usize, f32) {
+    let height = (ceiling_y - DOOR_HEADROOM - floor_y).max(0.0);
+    let max_rise = jump_height(player) * JUMP_MARGIN;
+    if height <= max_rise {
+        return (1, height);
+    }
+    let count = (height / max_rise).ceil();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (count as usize, height / count)
+}
+
+/// the platform standing the given height above the floor, topside level with it
+fn platform_at(x: f32, floor_y: f32, height: f32) -> Vec2 {
+    Vec2::new(x, floor_y + height - PLATFORM_HEIGHT / 2.0)
+}
+
+/**
+ * the choice for a room, by the kind of resource it stands for - rooms that are not a resource,
+ * like the lobby and hallways, have lots of doors so default to tiers
+ */
+pub fn room_layout(choices: &[(String, RoomLayout)], kind: Option<&str>) -> RoomLayout {
+    let choice = |kind: &str| {
+        choices
+            .iter()
+            .rev()
+            .find(|(k, _)| k.eq_ignore_ascii_case(kind))
+            .map(|(_, layout)| *layout)
+    };
+    let fallback = if kind.is_some() {
+        RoomLayout::Row
+    } else {
+        RoomLayout::Tiers
+    };
+    kind.and_then(choice)
+        .or_else(|| choice("default"))
+        .unwrap_or(fallback)
+}
+
+/**
+ * platforms for the doors of a room between the floor and ceiling given, every platform within a
+ * jump of the floor or of another platform
+ */
+pub fn layout_platforms(
+    layout: RoomLayout,
+    number_of_doors: usize,
+    floor_y: f32,
+    ceiling_y: f32,
+    settings: &Settings,
+) -> Layout {
+    let (tier_count, rise) = tiers(floor_y, ceiling_y, &settings.player);
+    let separator = settings.platforms.x_separator;
+    match layout {
+        RoomLayout::Row => row(number_of_doors, floor_y, rise, separator),
+        RoomLayout::Tiers => stacked_rows(number_of_doors, floor_y, tier_count, rise, separator),
+        RoomLayout::Ledges => {
+            let step = LEDGE_SEPARATOR.min(jump_distance(&settings.player));
+            ledges(number_of_doors, floor_y, tier_count, rise, step)
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn row(number_of_doors: usize, floor_y: f32, rise: f32, separator: f32) -> Layout {
+    let room_width = separator * (number_of_doors + 1) as f32;
+    let positions = (0..number_of_doors)
+        .map(|i| {
+            let x = separator.mul_add((i + 1) as f32, -room_width / 2.0);
+            platform_at(x, floor_y, rise * ROW_HEIGHTS[i % ROW_HEIGHTS.len()])
+        })
+        .collect();
+    Layout {
+        room_width,
+        positions,
+    }
+}
+
+/**
+ * rows filled from the bottom up, every other row shifted half a gap along so there is a platform
+ * below to jump up from without the one above in the way
+ */
+#[allow(clippy::cast_precision_loss)]
+fn stacked_rows(
+    number_of_doors: usize,
+    floor_y: f32,
+    tier_count: usize,
+    rise: f32,
+    separator: f32,
+) -> Layout {
+    let columns = number_of_doors.div_ceil(tier_count).max(1);
+    let room_width = separator * (columns + 1) as f32;
+    let positions = (0..number_of_doors)
+        .map(|i| {
+            let (tier, column) = (i / columns, i % columns);
+            let shift = if tier % 2 == 0 { 0.0 } else { separator / 2.0 };
+            let x = separator.mul_add((column + 1) as f32, shift - room_width / 2.0);
+            platform_at(x, floor_y, rise * (tier + 1) as f32)
+        })
+        .collect();
+    Layout {
+        room_width,
+        positions,
+    }
+}
+
+/// a staircase up to the top tier and back down, over and over
+#[allow(clippy::cast_precision_loss)]
+fn ledges(number_of_doors: usize, floor_y: f32, tier_count: usize, rise: f32, step: f32) -> Layout {
+    let room_width = step * (number_of_doors + 1) as f32;
+    let period = 2 * (tier_count - 1);
+    let positions = (0..number_of_doors)
+        .map(|i| {
+            let phase = if period == 0 { 0 } else { i % period };
+            let tier = if phase < tier_count {
+                phase
+            } else {
+                period - phase
+            };
+            let x = step.mul_add((i + 1) as f32, -room_width / 2.0);
+            platform_at(x, floor_y, rise * (tier + 1) as f32)
+        })
+        .collect();
+    Layout {
+        room_width,
+        positions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLOOR_Y: f32 = -400.0;
+    const CEILING_Y: f32 = 400.0;
+    const LAYOUTS: [RoomLayout; 3] = [RoomLayout::Row, RoomLayout::Tiers, RoomLayout::Ledges];
+
+    fn top(position: Vec2) -> f32 {
+        position.y + PLATFORM_HEIGHT / 2.0
+    }
+
+    #[test]
+    fn test_platforms_fit_in_the_room() {
+        for layout in LAYOUTS {
+            for doors in [0, 1, 2, 7, 30] {
+                let Layout {
+                    room_width,
+                    positions,
+                } = layout_platforms(layout, doors, FLOOR_Y, CEILING_Y, &Settings::default());
+                assert_eq!(positions.len(), doors);
+                for position in positions {
+                    assert!(position.x.abs() + PLATFORM_WIDTH / 2.0 < room_width / 2.0);
+                    assert!(top(position) > FLOOR_Y);
+                    assert!(top(position) + DOOR_HEADROOM <= CEILING_Y + 0.01);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_platform_is_within_a_jump() {
+        let settings = Settings::default();
+        let (jump_height, jump_distance) = (
+            jump_height(&settings.player),
+            jump_distance(&settings.player),
+        );
+        for layout in LAYOUTS {
+            let positions = layout_platforms(layout, 30, FLOOR_Y, CEILING_Y, &settings).positions;
+            for position in &positions {
+                let from_floor = top(*position) - FLOOR_Y <= jump_height;
+                let from_platform = positions.iter().any(|other| {
+                    let rise = top(*position) - top(*other);
+                    rise > 0.0
+                        && rise <= jump_height
+                        && (position.x - other.x).abs() <= jump_distance
+                });
+                assert!(from_floor || from_platform, "{layout:?} {position}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_tiers_use_the_full_height() {
+        let settings = Settings::default();
+        let positions =
+            layout_platforms(RoomLayout::Tiers, 30, FLOOR_Y, CEILING_Y, &settings).positions;
+        let highest = positions.iter().map(|p| top(*p)).fold(FLOOR_Y, f32::max);
+        assert!((highest + DOOR_HEADROOM - CEILING_Y).abs() < 0.01);
+        assert!(tiers(FLOOR_Y, CEILING_Y, &settings.player).0 > 1);
+    }
+
+    #[test]
+    fn test_room_layout_choice() {
+        let choices = vec![
+            ("Pod".to_string(), RoomLayout::Ledges),
+            ("default".to_string(), RoomLayout::Row),
+        ];
+        assert_eq!(room_layout(&choices, Some("pod")), RoomLayout::Ledges);
+        assert_eq!(room_layout(&choices, Some("Service")), RoomLayout::Row);
+        assert_eq!(room_layout(&choices, None), RoomLayout::Row);
+        assert_eq!(room_layout(&[], None), RoomLayout::Tiers);
+        assert_eq!(room_layout(&[], Some("Pod")), RoomLayout::Row);
+    }
+}
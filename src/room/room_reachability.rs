@@ -0,0 +1,153 @@
+use super::room_layout::{PHYSICS_STEP, gravity, jump_height};
+use crate::door::door_component::{PLATFORM_HEIGHT, PLATFORM_WIDTH};
+use crate::settings::PlayerSettings;
+use bevy::prelude::*;
+
+const REPAIR_MARGIN: f32 = 0.8; // repaired platforms shouldn't need a perfect jump either
+
+/**
+ * where the player's feet go in a jump with a move key held from a standstill, one point per
+ * physics step until they drop the given depth below where they jumped from
+ */
+pub fn jump_arc(depth: f32, player: &PlayerSettings) -> Vec<Vec2> {
+    let mut velocity = Vec2::new(0.0, player.jump_speed);
+    let mut position = Vec2::ZERO;
+    let mut arc = vec![position];
+    loop {
+        velocity.x = player
+            .air_acceleration
+            .mul_add(PHYSICS_STEP, velocity.x)
+            .min(player.max_speed);
+        velocity.y -= gravity(player) * PHYSICS_STEP;
+        position += velocity * PHYSICS_STEP;
+        if position.y < -depth {
+            return arc;
+        }
+        arc.push(position);
+    }
+}
+
+/**
+ * how far sideways the player can get and still land on something the given height above where
+ * they jumped from, none when it is out of reach - letting go of the move key lands anywhere closer
+ */
+fn reach(arc: &[Vec2], rise: f32) -> Option<f32> {
+    arc.iter()
+        .rev()
+        .find(|point| point.y >= rise)
+        .map(|point| point.x)
+}
+
+fn top(position: Vec2) -> f32 {
+    position.y + PLATFORM_HEIGHT / 2.0
+}
+
+/// the sideways gap between the edges of two platforms, none when one is over the other
+fn gap(from: Vec2, to: Vec2) -> f32 {
+    ((from.x - to.x).abs() - PLATFORM_WIDTH).max(0.0)
+}
+
+/**
+ * the platforms no series of jumps from the floor lands on - the floor runs the width of the room
+ * so it's only the height that counts from there
+ */
+pub fn unreachable_platforms(
+    positions: &[Vec2],
+    floor_y: f32,
+    player: &PlayerSettings,
+) -> Vec<usize> {
+    let depth = positions
+        .iter()
+        .map(|p| top(*p) - floor_y)
+        .fold(0.0, f32::max);
+    let arc = jump_arc(depth, player);
+    let mut reached: Vec<bool> = positions
+        .iter()
+        .map(|p| reach(&arc, top(*p) - floor_y).is_some())
+        .collect();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for to in 0..positions.len() {
+            if reached[to] {
+                continue;
+            }
+            reached[to] = (0..positions.len()).any(|from| {
+                reached[from]
+                    && reach(&arc, top(positions[to]) - top(positions[from]))
+                        .is_some_and(|distance| distance >= gap(positions[from], positions[to]))
+            });
+            changed |= reached[to];
+        }
+    }
+    (0..positions.len()).filter(|i| !reached[*i]).collect()
+}
+
+/**
+ * drops every unreachable platform to a height that can be jumped to from the floor, returning
+ * the ones moved
+ */
+pub fn repair_unreachable_platforms(
+    positions: &mut [Vec2],
+    floor_y: f32,
+    player: &PlayerSettings,
+) -> Vec<usize> {
+    let unreachable = unreachable_platforms(positions, floor_y, player);
+    for i in &unreachable {
+        positions[*i].y =
+            jump_height(player).mul_add(REPAIR_MARGIN, floor_y) - PLATFORM_HEIGHT / 2.0;
+    }
+    unreachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLOOR_Y: f32 = -400.0;
+
+    fn platform(x: f32, height: f32) -> Vec2 {
+        Vec2::new(x, FLOOR_Y + height - PLATFORM_HEIGHT / 2.0)
+    }
+
+    #[test]
+    fn test_jump_arc() {
+        let player = PlayerSettings::default();
+        let arc = jump_arc(0.0, &player);
+        let highest = arc.iter().map(|p| p.y).fold(0.0, f32::max);
+        let jump_height = jump_height(&player);
+        assert!((highest - jump_height).abs() < jump_height * 0.05);
+        assert!(arc.windows(2).all(|pair| pair[1].x >= pair[0].x));
+    }
+
+    #[test]
+    fn test_unreachable_platforms() {
+        let player = PlayerSettings::default();
+        let high = jump_height(&player) * 0.9;
+        let positions = vec![
+            platform(0.0, high),
+            platform(300.0, high * 1.8),  // from the first
+            platform(5000.0, high * 1.8), // too far from the others
+            platform(600.0, high * 3.0),  // too high above the second
+        ];
+        assert_eq!(
+            unreachable_platforms(&positions, FLOOR_Y, &player),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn test_repair_unreachable_platforms() {
+        let player = PlayerSettings::default();
+        let mut positions = vec![
+            platform(0.0, jump_height(&player) * 0.5),
+            platform(900.0, 700.0),
+        ];
+        assert_eq!(
+            repair_unreachable_platforms(&mut positions, FLOOR_Y, &player),
+            vec![1]
+        );
+        assert_eq!(positions[1].x, 900.0);
+        assert!(unreachable_platforms(&positions, FLOOR_Y, &player).is_empty());
+    }
+}
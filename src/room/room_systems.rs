@@ -1,17 +1,40 @@
 use super::room_component::{Ceiling, CurrentFloorPlan, Floor, LeftWall, RightWall, RoomState};
+use super::room_door_order::order_doors;
+use super::room_layout::{Layout, layout_platforms, room_layout};
+use super::room_reachability::repair_unreachable_platforms;
 use crate::{
-    constants::WINDOW_HEIGHT,
-    floorplan,
+    cli::{Cli, DoorGrouping, RoomLayout},
+    constants::ROOM_HEIGHT,
+    floorplan::{self, FloorPlan, FloorPlanResult},
     room::room_component::DoorState,
+    settings::{Settings, settings_component::SurfaceSettings},
     state::{GameState, state_component::FadeEffect},
 };
 use avian2d::{parry::shape::SharedShape, prelude::*};
 use bevy::prelude::*;
+use clap::Parser;
 
-const PLATFORM_X_SEPARATOR: f32 = 450.0;
-const PLATFORM_Y_SEPARATOR: &[f32] = &[
-    0.0, -150.0, 120.0, -100.0, -180.0, 100.0, -200.0, -50.0, 165.0, 25.0,
-];
+/**
+* rooms with too many doors are split into sections, whichever integration the floorplan came from
+*/
+fn partition_floorplan(floorplan: &mut FloorPlan) -> FloorPlanResult<()> {
+    let cli = Cli::parse();
+    let doors_per_section = cli.doors_per_section.unwrap_or(20);
+    match cli.door_grouping.unwrap_or_default() {
+        DoorGrouping::Alphabetical => floorplan.partition_large_rooms(doors_per_section, |_| None),
+        DoorGrouping::Owner => floorplan.partition_large_rooms(doors_per_section, |room| {
+            room.resource.as_ref().and_then(|r| r.owner.clone())
+        }),
+        DoorGrouping::Label => {
+            let label = cli.grouping_label.unwrap_or_default();
+            floorplan.partition_large_rooms(doors_per_section, |room| {
+                room.resource
+                    .as_ref()
+                    .and_then(|r| r.labels.get(&label).cloned())
+            })
+        }
+    }
+}
 
 pub fn handle_floor_plan_changes(
     mut next_state: ResMut<NextState<GameState>>,
@@ -24,7 +47,10 @@ pub fn handle_floor_plan_changes(
         debug!("Floor plan event received.");
         current_floorplan.refreshed = time.elapsed();
 
-        let new_floorplan = event.floorplan.clone();
+        let mut new_floorplan = event.floorplan.clone();
+        if let Err(e) = partition_floorplan(&mut new_floorplan) {
+            warn!("Could not split the big rooms into sections: {e:?}");
+        }
 
         let (you_are_here, you_were_here) =
             determine_current_location(&new_floorplan, &current_floorplan);
@@ -82,10 +108,20 @@ fn determine_current_location(
     )
 }
 
-pub fn update_doors(current_floorplan: Res<CurrentFloorPlan>, mut room_state: ResMut<RoomState>) {
+pub fn update_doors(
+    current_floorplan: Res<CurrentFloorPlan>,
+    settings: Res<Settings>,
+    mut room_state: ResMut<RoomState>,
+) {
     if !current_floorplan.is_changed() {
         return;
     }
+    // a refresh of the room we are in keeps its doors where they were
+    let previous_doors: Vec<String> = if room_state.room_id == current_floorplan.you_are_here {
+        room_state.doors.iter().map(|d| d.room_id.clone()).collect()
+    } else {
+        Vec::new()
+    };
     room_state.doors.clear();
     room_state
         .previous_room_id
@@ -94,58 +130,86 @@ pub fn update_doors(current_floorplan: Res<CurrentFloorPlan>, mut room_state: Re
         .room_id
         .clone_from(&current_floorplan.you_are_here);
 
+    room_state.attributes.clear();
+    room_state.health = floorplan::RoomHealth::Unknown;
+    room_state.resource = None;
+
     if let Some(floorplan) = current_floorplan.floorplan.as_ref()
         && let Some(room_id) = &current_floorplan.you_are_here
     {
+        if let Ok(room) = floorplan.get_room_by_id(room_id) {
+            room_state.attributes.clone_from(&room.attributes);
+            room_state.health = room.health;
+            room_state.resource.clone_from(&room.resource);
+        }
         match floorplan.get_doors_and_connected_rooms(room_id) {
-            Ok(doors_and_rooms) => {
-                update_room_state_with_doors(&mut room_state, doors_and_rooms);
+            Ok(mut doors_and_rooms) => {
+                let cli = Cli::parse();
+                order_doors(
+                    &mut doors_and_rooms,
+                    cli.door_order.unwrap_or_default(),
+                    &previous_doors,
+                );
+                let kind = room_state.resource.as_ref().map(|r| r.kind.as_str());
+                let layout = room_layout(&cli.room_layout, kind);
+                update_room_state_with_doors(&mut room_state, doors_and_rooms, layout, &settings);
             }
             _ => panic!("Failed to get doors and connected rooms"),
         }
     }
 }
 
+/// the top of the floor, where every jump in the room starts
+fn floor_top(room_state: &RoomState) -> f32 {
+    room_state
+        .boundary_thickness
+        .mul_add(2.0, -ROOM_HEIGHT / 2.0)
+}
+
+/// the underside of the ceiling, as high as the platforms can go
+fn ceiling_bottom(room_state: &RoomState) -> f32 {
+    room_state
+        .boundary_thickness
+        .mul_add(-2.0, ROOM_HEIGHT / 2.0)
+}
+
 fn update_room_state_with_doors(
     room_state: &mut RoomState,
-    doors_and_rooms: Vec<(&floorplan::DoorData, &floorplan::RoomData)>,
+    mut doors_and_rooms: Vec<(&floorplan::DoorData, &floorplan::RoomData)>,
+    layout: RoomLayout,
+    settings: &Settings,
 ) {
-    let number_of_doors = doors_and_rooms.len();
-    #[allow(clippy::cast_precision_loss)]
-    let room_width = PLATFORM_X_SEPARATOR * (number_of_doors + 1) as f32;
+    // the way out is always the first platform, at the left end and in reach of the floor
+    doors_and_rooms.sort_by_key(|(door_data, _)| !door_data.is_exit);
+
+    let floor_y = floor_top(room_state);
+    let ceiling_y = ceiling_bottom(room_state);
+    let Layout {
+        room_width,
+        positions: mut room_positions,
+    } = layout_platforms(layout, doors_and_rooms.len(), floor_y, ceiling_y, settings);
 
     room_state.wall_distance_from_center = room_width / 2.0;
     room_state.floor_ceiling_width = room_width;
 
-    let mut room_seq = 0;
-    let mut room_positions: Vec<Vec2> = (0..number_of_doors)
-        .map(|i| {
-            let y_index = if room_seq < PLATFORM_Y_SEPARATOR.len() {
-                room_seq
-            } else {
-                room_seq % PLATFORM_Y_SEPARATOR.len()
-            };
-            room_seq += 1;
+    // a layout leaving doors out of reach is a bug, but players shouldn't be stuck because of it
+    for i in repair_unreachable_platforms(&mut room_positions, floor_y, &settings.player) {
+        warn!(
+            "Platform {i} of the {layout:?} layout was out of reach, lowered to {}",
+            room_positions[i]
+        );
+    }
 
-            Vec2 {
-                #[allow(clippy::cast_precision_loss)]
-                x: PLATFORM_X_SEPARATOR.mul_add(i as f32, PLATFORM_X_SEPARATOR)
-                    - room_state.wall_distance_from_center,
-                y: PLATFORM_Y_SEPARATOR[y_index],
-            }
-        })
-        .collect();
-
-    for (door_data, room) in doors_and_rooms {
-        if let Some(position) = room_positions.pop() {
-            let door_state = DoorState {
-                room_id: room.id.clone(),
-                room_name: room.name.clone(),
-                position,
-                is_exit: door_data.is_exit,
-            };
-            room_state.doors.push(door_state);
-        }
+    // doors take the platforms in order, left to right and bottom up
+    for ((door_data, room), position) in doors_and_rooms.into_iter().zip(room_positions) {
+        let door_state = DoorState {
+            room_id: room.id.clone(),
+            room_name: room.name.clone(),
+            position,
+            is_exit: door_data.is_exit,
+            health: room.health,
+        };
+        room_state.doors.push(door_state);
     }
 }
 
@@ -187,11 +251,8 @@ fn update_ground(
             room_state.floor_ceiling_width / 2.0,
             room_state.boundary_thickness,
         ));
-        *transform = Transform::from_xyz(
-            0.0,
-            -WINDOW_HEIGHT / 2.0 + room_state.boundary_thickness,
-            0.0,
-        );
+        *transform =
+            Transform::from_xyz(0.0, -ROOM_HEIGHT / 2.0 + room_state.boundary_thickness, 0.0);
     }
 }
 
@@ -203,12 +264,12 @@ fn update_left_wall(
     for (mut transform, mut collider, mut sprite) in query.iter_mut() {
         *collider = Collider::from(SharedShape::cuboid(
             room_state.boundary_thickness,
-            WINDOW_HEIGHT / 2.0,
+            ROOM_HEIGHT / 2.0,
         ));
         *transform = Transform::from_xyz(x_position, 0.0, 0.0);
         sprite.custom_size = Some(Vec2::new(
             room_state.boundary_thickness * 200.0,
-            WINDOW_HEIGHT,
+            ROOM_HEIGHT,
         ));
     }
 }
@@ -221,12 +282,12 @@ fn update_right_wall(
     for (mut transform, mut collider, mut sprite) in query.iter_mut() {
         *collider = Collider::from(SharedShape::cuboid(
             room_state.boundary_thickness,
-            WINDOW_HEIGHT / 2.0,
+            ROOM_HEIGHT / 2.0,
         ));
         *transform = Transform::from_xyz(x_position, 0.0, 0.0);
         sprite.custom_size = Some(Vec2::new(
             room_state.boundary_thickness * 200.0,
-            WINDOW_HEIGHT,
+            ROOM_HEIGHT,
         ));
     }
 }
@@ -240,11 +301,8 @@ fn update_ceiling(
             room_state.floor_ceiling_width / 2.0,
             room_state.boundary_thickness,
         ));
-        *transform = Transform::from_xyz(
-            0.0,
-            WINDOW_HEIGHT / 2.0 - room_state.boundary_thickness,
-            0.0,
-        );
+        *transform =
+            Transform::from_xyz(0.0, ROOM_HEIGHT / 2.0 - room_state.boundary_thickness, 0.0);
     }
 }
 
@@ -252,49 +310,41 @@ pub fn setup_room(
     mut commands: Commands,
     room_state: ResMut<RoomState>,
     current_floorplan: Res<CurrentFloorPlan>,
+    settings: Res<Settings>,
 ) {
     if current_floorplan.you_are_here.is_some() {
         // room is already setup
         return;
     }
 
-    spawn_floor(&mut commands, &room_state);
+    spawn_floor(&mut commands, &room_state, settings.floor);
     spawn_wall(
         &mut commands,
         &room_state,
+        settings.walls,
         -room_state.wall_distance_from_center,
         LeftWall,
     );
     spawn_wall(
         &mut commands,
         &room_state,
+        settings.walls,
         room_state.wall_distance_from_center,
         RightWall,
     );
-    spawn_ceiling(&mut commands, &room_state);
+    spawn_ceiling(&mut commands, &room_state, settings.ceiling);
 }
 
-fn spawn_floor(commands: &mut Commands, room_state: &RoomState) {
+fn spawn_floor(commands: &mut Commands, room_state: &RoomState, surface: SurfaceSettings) {
     commands.spawn((
         RigidBody::Static,
         Collider::from(SharedShape::cuboid(
             room_state.floor_ceiling_width,
             room_state.boundary_thickness,
         )),
-        Transform::from_xyz(
-            0.0,
-            -WINDOW_HEIGHT / 2.0 + room_state.boundary_thickness,
-            0.0,
-        ),
-        Friction {
-            dynamic_coefficient: 0.8,
-            static_coefficient: 0.9,
-            combine_rule: CoefficientCombine::Average,
-        },
-        Restitution {
-            coefficient: room_state.bounce_effect % 2.0,
-            combine_rule: CoefficientCombine::Max,
-        },
+        Transform::from_xyz(0.0, -ROOM_HEIGHT / 2.0 + room_state.boundary_thickness, 0.0),
+        surface.friction.friction(),
+        surface.restitution(),
         Floor,
     ));
 }
@@ -302,6 +352,7 @@ fn spawn_floor(commands: &mut Commands, room_state: &RoomState) {
 fn spawn_wall(
     commands: &mut Commands,
     room_state: &RoomState,
+    surface: SurfaceSettings,
     x_position: f32,
     wall_type: impl Component,
 ) {
@@ -309,23 +360,16 @@ fn spawn_wall(
         RigidBody::Static,
         Collider::from(SharedShape::cuboid(
             room_state.boundary_thickness,
-            WINDOW_HEIGHT / 2.0,
+            ROOM_HEIGHT / 2.0,
         )),
         Transform::from_xyz(x_position, 0.0, 0.0),
-        Friction {
-            dynamic_coefficient: 0.5,
-            static_coefficient: 0.6,
-            combine_rule: CoefficientCombine::Average,
-        },
-        Restitution {
-            coefficient: room_state.bounce_effect,
-            combine_rule: CoefficientCombine::Max,
-        },
+        surface.friction.friction(),
+        surface.restitution(),
         Sprite {
             color: Color::srgb(0.5, 0.5, 0.5), // Matching the platform color
             custom_size: Some(Vec2::new(
                 room_state.boundary_thickness * 200.0,
-                WINDOW_HEIGHT,
+                ROOM_HEIGHT,
             )),
             ..default()
         },
@@ -333,22 +377,120 @@ fn spawn_wall(
     ));
 }
 
-fn spawn_ceiling(commands: &mut Commands, room_state: &RoomState) {
+fn spawn_ceiling(commands: &mut Commands, room_state: &RoomState, surface: SurfaceSettings) {
     commands.spawn((
         RigidBody::Static,
         Collider::from(SharedShape::cuboid(
             room_state.floor_ceiling_width,
             room_state.boundary_thickness,
         )),
-        Transform::from_xyz(
-            0.0,
-            WINDOW_HEIGHT / 2.0 - room_state.boundary_thickness,
-            0.0,
-        ),
-        Restitution {
-            coefficient: room_state.bounce_effect,
-            combine_rule: CoefficientCombine::Max,
-        },
+        Transform::from_xyz(0.0, ROOM_HEIGHT / 2.0 - room_state.boundary_thickness, 0.0),
+        surface.friction.friction(),
+        surface.restitution(),
         Ceiling,
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::door::door_component::PLATFORM_HEIGHT;
+    #[cfg(feature = "k8s")]
+    use crate::integration::k8s_file::k8s_integration_systems::generate_k8s_floorplan_from_file;
+    use crate::integration::test_mode::test_mode_systems::generate_room25_floorplan;
+    use crate::room::room_layout::jump_height;
+    use crate::room::room_reachability::unreachable_platforms;
+    use crate::settings::PlayerSettings;
+
+    const LAYOUTS: [RoomLayout; 3] = [RoomLayout::Row, RoomLayout::Tiers, RoomLayout::Ledges];
+
+    /**
+     * every door of every room, in every layout, can be jumped to from the floor - checked on the
+     * platforms as laid out, before any out of reach are lowered
+     */
+    fn assert_every_door_reachable(floorplan: &FloorPlan) {
+        let room_state = RoomState::default();
+        let floor_y = floor_top(&room_state);
+        let ceiling_y = ceiling_bottom(&room_state);
+        let settings = Settings::default();
+        for room_id in floorplan.get_all_room_ids() {
+            let number_of_doors = floorplan
+                .get_doors_and_connected_rooms(&room_id)
+                .expect("doors expected")
+                .len();
+            for layout in LAYOUTS {
+                let Layout { positions, .. } =
+                    layout_platforms(layout, number_of_doors, floor_y, ceiling_y, &settings);
+                assert_eq!(positions.len(), number_of_doors);
+                let unreachable = unreachable_platforms(&positions, floor_y, &settings.player);
+                assert!(
+                    unreachable.is_empty(),
+                    "{room_id} {layout:?} unreachable: {unreachable:?}"
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "k8s")]
+    #[test]
+    fn test_exits_at_the_left_end_in_reach_of_the_floor() {
+        let floorplan = generate_k8s_floorplan_from_file().expect("floorplan expected");
+        let mut rooms_with_exits = 0;
+        for room_id in floorplan.get_all_room_ids() {
+            for layout in LAYOUTS {
+                let mut room_state = RoomState::default();
+                let doors_and_rooms = floorplan
+                    .get_doors_and_connected_rooms(&room_id)
+                    .expect("doors expected");
+                update_room_state_with_doors(
+                    &mut room_state,
+                    doors_and_rooms,
+                    layout,
+                    &Settings::default(),
+                );
+                let Some(exit) = room_state.doors.first().filter(|door| door.is_exit) else {
+                    assert!(room_state.doors.iter().all(|door| !door.is_exit));
+                    continue;
+                };
+                rooms_with_exits += 1;
+                assert_eq!(
+                    room_state.doors.iter().filter(|door| door.is_exit).count(),
+                    1,
+                    "{room_id} has more than one exit"
+                );
+                let leftmost = room_state
+                    .doors
+                    .iter()
+                    .map(|d| d.position.x)
+                    .fold(f32::MAX, f32::min);
+                assert!(
+                    (exit.position.x - leftmost).abs() < f32::EPSILON,
+                    "{room_id} {layout:?}"
+                );
+                let height = exit.position.y + PLATFORM_HEIGHT / 2.0 - floor_top(&room_state);
+                assert!(
+                    height <= jump_height(&PlayerSettings::default()),
+                    "{room_id} {layout:?}"
+                );
+            }
+        }
+        assert!(rooms_with_exits > 0);
+    }
+
+    #[test]
+    fn test_every_door_reachable_in_generated_rooms() {
+        let floorplan = generate_room25_floorplan().expect("floorplan expected");
+        assert_every_door_reachable(&floorplan);
+    }
+
+    #[cfg(feature = "k8s")]
+    #[test]
+    fn test_every_door_reachable_in_k8s_rooms() {
+        let mut floorplan = generate_k8s_floorplan_from_file().expect("floorplan expected");
+        floorplan
+            .partition_large_rooms(20, |_| None)
+            .expect("sections expected");
+        assert!(floorplan.get_all_room_ids().len() > 25);
+        assert_every_door_reachable(&floorplan);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod settings_component;
+pub mod settings_plugin;
+pub mod settings_systems;
+
+pub use settings_component::{CameraSettings, PlayerSettings, Settings};
+pub use settings_plugin::SettingsPlugin;
@@ -0,0 +1,348 @@
+use avian2d::prelude::{CoefficientCombine, Friction, Restitution};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::state::state_component::TransitionStyle;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// the settings the game ships with, a settings file only needs what it changes
+const DEFAULT_SETTINGS: &str = include_str!("../../assets/settings.yaml");
+
+const FAST_TRAVEL_SPEED: f32 = 12.0; // door walks, fades and curtain in about half a second
+
+/// how the game feels, from the settings file
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub player: PlayerSettings,
+    pub camera: CameraSettings,
+    pub transitions: TransitionSettings,
+    pub floor: SurfaceSettings,
+    pub walls: SurfaceSettings,
+    pub ceiling: SurfaceSettings,
+    pub platforms: PlatformSettings,
+}
+
+impl Default for Settings {
+    #[allow(clippy::expect_used)] // the defaults are checked by the tests
+    fn default() -> Self {
+        serde_yaml::from_str(DEFAULT_SETTINGS).expect("the default settings should parse")
+    }
+}
+
+impl Settings {
+    /// the settings in the file over the defaults, so long as the game can be played with them
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        let mut settings: Value = serde_yaml::from_str(DEFAULT_SETTINGS)?;
+        merge(&mut settings, serde_yaml::from_str(yaml)?);
+        let settings: Self = serde_yaml::from_value(settings)?;
+        settings
+            .validate()
+            .map_err(<serde_yaml::Error as serde::de::Error>::custom)?;
+        Ok(settings)
+    }
+
+    /// values that would leave the player stuck or the physics inside out - the comparisons are
+    /// negated so `.nan`, which fails every comparison, is turned away too
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn validate(&self) -> Result<(), String> {
+        let player = &self.player;
+        let camera = &self.camera;
+        let positive = [
+            ("player.gravity_scale", player.gravity_scale),
+            ("player.jump_speed", player.jump_speed),
+            ("player.max_speed", player.max_speed),
+            ("player.ground_acceleration", player.ground_acceleration),
+            ("player.air_acceleration", player.air_acceleration),
+            ("platforms.x_separator", self.platforms.x_separator),
+            ("camera.min_zoom", camera.min_zoom),
+            ("camera.follow_speed", camera.follow_speed),
+            ("camera.zoom_speed", camera.zoom_speed),
+        ];
+        if let Some((name, value)) = positive.iter().find(|(_, value)| !(*value > 0.0)) {
+            return Err(format!("{name} must be more than 0, not {value}"));
+        }
+        let not_negative = [
+            ("camera.deadzone.width", camera.deadzone.width),
+            ("camera.deadzone.height", camera.deadzone.height),
+        ];
+        if let Some((name, value)) = not_negative.iter().find(|(_, value)| !(*value >= 0.0)) {
+            return Err(format!("{name} must be 0 or more, not {value}"));
+        }
+        if !(0.0..=1.0).contains(&player.jump_cut) {
+            return Err(format!(
+                "player.jump_cut must be from 0 to 1, not {}",
+                player.jump_cut
+            ));
+        }
+        if !(camera.min_zoom <= camera.max_zoom) {
+            return Err(format!(
+                "camera.min_zoom {} is more than camera.max_zoom {}",
+                camera.min_zoom, camera.max_zoom
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn merge(base: &mut Value, over: Value) {
+    match (base, over) {
+        (Value::Mapping(base), Value::Mapping(over)) => {
+            for (key, value) in over {
+                match base.get_mut(&key) {
+                    Some(base_value) => merge(base_value, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (_, Value::Null) => (), // an empty file, or section
+        (base, over) => *base = over,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlayerSettings {
+    pub max_speed: f32,
+    pub ground_acceleration: f32,
+    pub air_acceleration: f32,
+    pub jump_speed: f32,
+    pub jump_cut: f32,
+    pub gravity_scale: f32,
+    pub coyote_time: f32,
+    pub jump_buffer: f32,
+    pub friction: FrictionSettings,
+}
+
+impl Default for PlayerSettings {
+    fn default() -> Self {
+        Settings::default().player
+    }
+}
+
+/// zoom is how much bigger than usual the room is drawn
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CameraSettings {
+    pub deadzone: DeadzoneSettings,
+    pub follow_speed: f32,
+    pub zoom_speed: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+}
+
+/// how the screen goes dark through a door and light again in the next room
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransitionSettings {
+    pub leaving: TransitionStyle,
+    pub arriving: TransitionStyle,
+    pub fast_travel: bool,
+}
+
+impl TransitionSettings {
+    /// how many times faster than usual a transition runs, with skip held or fast travel on
+    pub const fn speed(self, skipping: bool) -> f32 {
+        if skipping || self.fast_travel {
+            FAST_TRAVEL_SPEED
+        } else {
+            1.0
+        }
+    }
+}
+
+/// the box in the middle of the screen the player moves about in without the camera following
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeadzoneSettings {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl DeadzoneSettings {
+    pub const fn half_size(self) -> Vec2 {
+        Vec2::new(self.width / 2.0, self.height / 2.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FrictionSettings {
+    pub dynamic: f32,
+    #[serde(rename = "static")]
+    pub static_: f32,
+}
+
+impl FrictionSettings {
+    pub const fn friction(self) -> Friction {
+        Friction {
+            dynamic_coefficient: self.dynamic,
+            static_coefficient: self.static_,
+            combine_rule: CoefficientCombine::Average,
+        }
+    }
+}
+
+/// the floor, walls and ceiling
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SurfaceSettings {
+    pub friction: FrictionSettings,
+    pub restitution: f32,
+}
+
+impl SurfaceSettings {
+    pub const fn restitution(self) -> Restitution {
+        Restitution {
+            coefficient: self.restitution,
+            combine_rule: CoefficientCombine::Max,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlatformSettings {
+    pub friction: FrictionSettings,
+    pub restitution: f32,
+    pub x_separator: f32,
+}
+
+impl PlatformSettings {
+    pub const fn surface(&self) -> SurfaceSettings {
+        SurfaceSettings {
+            friction: self.friction,
+            restitution: self.restitution,
+        }
+    }
+}
+
+/// the settings file on disk and when it last changed, it's looked at every so often for edits
+#[derive(Resource)]
+pub struct SettingsFile {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+    pub timer: Timer,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_settings() {
+        let settings = Settings::default();
+        assert_eq!(settings.player.max_speed, 350.0);
+        assert_eq!(settings.platforms.restitution, 0.1);
+        assert_eq!(settings.platforms.x_separator, 450.0);
+    }
+
+    #[test]
+    fn test_settings_file_only_needs_what_it_changes() {
+        let settings = Settings::from_yaml(
+            "player:\n  jump_speed: 450.0\nwalls:\n  friction: { dynamic: 0.1 }\n",
+        )
+        .expect("settings expected");
+        let defaults = Settings::default();
+
+        assert_eq!(settings.player.jump_speed, 450.0);
+        assert_eq!(settings.player.max_speed, defaults.player.max_speed);
+        assert_eq!(settings.walls.friction.dynamic, 0.1);
+        assert_eq!(
+            settings.walls.friction.static_,
+            defaults.walls.friction.static_
+        );
+        assert_eq!(
+            Settings::from_yaml("").expect("settings expected"),
+            defaults
+        );
+        assert_eq!(settings.floor, defaults.floor);
+        assert_eq!(settings.platforms, defaults.platforms);
+    }
+
+    #[test]
+    fn test_transition_styles() {
+        let settings =
+            Settings::from_yaml("transitions:\n  leaving: door_zoom\n").expect("settings expected");
+        assert_eq!(settings.transitions.leaving, TransitionStyle::DoorZoom);
+        assert_eq!(
+            settings.transitions.arriving,
+            Settings::default().transitions.arriving
+        );
+        assert!(Settings::from_yaml("transitions:\n  leaving: wipe\n").is_err());
+    }
+
+    #[test]
+    fn test_fast_travel() {
+        let transitions = Settings::default().transitions;
+        assert!(!transitions.fast_travel);
+        assert_eq!(transitions.speed(false), 1.0);
+        assert_eq!(transitions.speed(true), FAST_TRAVEL_SPEED);
+
+        let settings =
+            Settings::from_yaml("transitions:\n  fast_travel: true\n").expect("settings expected");
+        assert_eq!(settings.transitions.speed(false), FAST_TRAVEL_SPEED);
+    }
+
+    #[test]
+    fn test_unknown_settings_are_an_error() {
+        assert!(Settings::from_yaml("player:\n  jump_sped: 1.0\n").is_err());
+        assert!(Settings::from_yaml("player:\n  jump_speed: high\n").is_err());
+    }
+
+    #[test]
+    fn test_speeds_gravity_and_spacing_must_be_positive() {
+        for (section, name) in [
+            ("player", "gravity_scale"),
+            ("player", "jump_speed"),
+            ("player", "max_speed"),
+            ("player", "ground_acceleration"),
+            ("player", "air_acceleration"),
+            ("platforms", "x_separator"),
+            ("camera", "min_zoom"),
+            ("camera", "follow_speed"),
+            ("camera", "zoom_speed"),
+        ] {
+            for value in ["0.0", "-1.0", ".nan"] {
+                let yaml = format!("{section}:\n  {name}: {value}\n");
+                let error = Settings::from_yaml(&yaml).expect_err(&yaml);
+                assert!(error.to_string().contains(name), "{error}");
+            }
+            let yaml = format!("{section}:\n  {name}: 0.5\n");
+            assert!(Settings::from_yaml(&yaml).is_ok(), "{yaml}");
+        }
+        // the deadzone may be nothing at all, just not less
+        for name in ["width", "height"] {
+            for value in ["-1.0", ".nan"] {
+                let yaml = format!("camera:\n  deadzone:\n    {name}: {value}\n");
+                let error = Settings::from_yaml(&yaml).expect_err(&yaml);
+                assert!(error.to_string().contains(name), "{error}");
+            }
+            let yaml = format!("camera:\n  deadzone:\n    {name}: 0.0\n");
+            assert!(Settings::from_yaml(&yaml).is_ok(), "{yaml}");
+        }
+    }
+
+    #[test]
+    fn test_jump_cut_from_0_to_1() {
+        for jump_cut in ["0.0", "0.5", "1.0"] {
+            let yaml = format!("player:\n  jump_cut: {jump_cut}\n");
+            assert!(Settings::from_yaml(&yaml).is_ok(), "{yaml}");
+        }
+        for jump_cut in ["-0.1", "1.5"] {
+            let yaml = format!("player:\n  jump_cut: {jump_cut}\n");
+            assert!(Settings::from_yaml(&yaml).is_err(), "{yaml}");
+        }
+    }
+
+    #[test]
+    fn test_min_zoom_not_over_max_zoom() {
+        assert!(Settings::from_yaml("camera:\n  min_zoom: 1.0\n  max_zoom: 1.0\n").is_ok());
+        assert!(Settings::from_yaml("camera:\n  min_zoom: 2.0\n  max_zoom: 1.0\n").is_err());
+        assert!(Settings::from_yaml("camera:\n  max_zoom: .nan\n").is_err());
+    }
+}
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use clap::Parser;
+
+use super::settings_component::SettingsFile;
+use super::settings_systems::{apply_settings, read_settings, reload_settings};
+use crate::cli::Cli;
+use crate::schedule::InGameSet;
+
+const SETTINGS_POLL_SECS: f32 = 0.5;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        // read before anything is spawned, so there is never a moment with the wrong settings
+        let path = Cli::parse().settings;
+        let (settings, modified) = read_settings(&path).unwrap_or_default();
+        app.insert_resource(settings)
+            .insert_resource(SettingsFile {
+                path,
+                modified,
+                timer: Timer::from_seconds(SETTINGS_POLL_SECS, TimerMode::Repeating),
+            })
+            .add_systems(
+                Update,
+                (reload_settings, apply_settings)
+                    .chain()
+                    .in_set(InGameSet::Update),
+            );
+    }
+}
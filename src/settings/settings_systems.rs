@@ -0,0 +1,104 @@
+use avian2d::prelude::{Friction, GravityScale, Restitution};
+use bevy::prelude::*;
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::settings_component::{Settings, SettingsFile, SurfaceSettings};
+use crate::door::Platform;
+use crate::player::Player;
+use crate::room::room_component::{Ceiling, Floor, LeftWall, RightWall};
+
+/// the settings in the file and when it was last changed, none when it can't be read
+pub fn read_settings(path: &Path) -> Option<(Settings, Option<SystemTime>)> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let yaml = std::fs::read_to_string(path).ok()?;
+    match Settings::from_yaml(&yaml) {
+        Ok(settings) => Some((settings, modified)),
+        Err(e) => {
+            warn!("ignoring settings in {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/**
+ * picks up edits to the settings file - a broken edit keeps the settings there were, the game
+ * shouldn't stop while a designer is mid-way through typing
+ */
+pub fn reload_settings(
+    time: Res<Time<Real>>,
+    mut file: ResMut<SettingsFile>,
+    mut settings: ResMut<Settings>,
+) {
+    if !file.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let modified = std::fs::metadata(&file.path)
+        .and_then(|m| m.modified())
+        .ok();
+    if modified.is_none() || modified == file.modified {
+        return;
+    }
+    file.modified = modified;
+    if let Some((new_settings, _)) = read_settings(&file.path)
+        && *settings != new_settings
+    {
+        info!("settings reloaded from {}", file.path.display());
+        *settings = new_settings;
+    }
+}
+
+const fn apply_surface(
+    surface: SurfaceSettings,
+    friction: &mut Friction,
+    restitution: &mut Restitution,
+) {
+    *friction = surface.friction.friction();
+    *restitution = surface.restitution();
+}
+
+/**
+ * everything already in the room takes on the new settings, the platform layout waits for the next
+ * room
+ */
+#[allow(clippy::type_complexity)]
+pub fn apply_settings(
+    settings: Res<Settings>,
+    mut player_query: Query<(&mut GravityScale, &mut Friction), With<Player>>,
+    mut surface_query: Query<
+        (
+            &mut Friction,
+            &mut Restitution,
+            Has<Floor>,
+            Has<LeftWall>,
+            Has<RightWall>,
+            Has<Ceiling>,
+            Has<Platform>,
+        ),
+        Without<Player>,
+    >,
+) {
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    for (mut gravity, mut friction) in &mut player_query {
+        gravity.0 = settings.player.gravity_scale;
+        *friction = settings.player.friction.friction();
+    }
+    for (mut friction, mut restitution, floor, left_wall, right_wall, ceiling, platform) in
+        &mut surface_query
+    {
+        let surface = if floor {
+            settings.floor
+        } else if left_wall || right_wall {
+            settings.walls
+        } else if ceiling {
+            settings.ceiling
+        } else if platform {
+            settings.platforms.surface()
+        } else {
+            continue;
+        };
+        apply_surface(surface, &mut friction, &mut restitution);
+    }
+}
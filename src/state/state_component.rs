@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const DOOR_ZOOM: f32 = 3.0; // how close the camera gets to the door before the screen is dark
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum GameState {
@@ -6,17 +9,147 @@ pub enum GameState {
     Welcome,
     InGame,
     Paused,
+    Controls,     // rebinding keys, the game is frozen as when paused
+    EnteringDoor, // the door opens and the player walks in
     TransitioningOut,
     RoomChange,
     TransitioningIn,
+    LeavingDoor, // the player steps out of the door into the new room
+}
+
+/// the black pieces hanging off the camera that cover the view, around a round hole for the iris
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FadeOverlay {
+    Hole,
+    Top,
+    Bottom,
+    Left,
+    Right,
 }
 
-#[derive(Component)]
-pub struct FadeOverlay;
+impl FadeOverlay {
+    pub const ALL: [Self; 5] = [Self::Hole, Self::Top, Self::Bottom, Self::Left, Self::Right];
+
+    /**
+     * the part of the view the piece covers, given the middle of the hole and its radius - empty
+     * when the hole reaches past that side of the view
+     */
+    pub fn rect(self, view: Rect, center: Vec2, radius: f32) -> Rect {
+        let (min, max) = match self {
+            Self::Hole => (center - radius, center + radius),
+            Self::Top => (Vec2::new(view.min.x, center.y + radius), view.max),
+            Self::Bottom => (view.min, Vec2::new(view.max.x, center.y - radius)),
+            Self::Left => (
+                Vec2::new(view.min.x, center.y - radius),
+                Vec2::new(center.x - radius, center.y + radius),
+            ),
+            Self::Right => (
+                Vec2::new(center.x + radius, center.y - radius),
+                Vec2::new(view.max.x, center.y + radius),
+            ),
+        };
+        Rect {
+            min,
+            max: max.max(min),
+        }
+    }
+}
 
 #[derive(Resource)]
 pub struct FadeEffect {
     pub curtain: f32,     // Timer (0.0 = start, 1.0 = ready to open curtain)
     pub alpha: f32,       // Opacity (0.0 = visible, 1.0 = fully black)
     pub fading_out: bool, // Track whether fading in or out
+    pub zoom: f32,        // how much closer the transition has taken the camera
+}
+
+/// how the screen goes dark leaving a room and light again in the next
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionStyle {
+    #[default]
+    Fade,
+    Iris,     // a circle closing in on the player
+    DoorZoom, // the camera closes in on the player at the door as it fades
+}
+
+/// how the screen looks part way through a transition
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransitionLook {
+    pub alpha: f32,
+    pub opening: f32, // of the iris, 1 for the whole view and 0 for closed
+    pub zoom: f32,
+}
+
+impl TransitionStyle {
+    /// from nothing to see at 0 to a dark screen at 1
+    pub fn look(self, progress: f32) -> TransitionLook {
+        match self {
+            Self::Fade => TransitionLook {
+                alpha: progress,
+                opening: 0.0,
+                zoom: 1.0,
+            },
+            Self::Iris => TransitionLook {
+                alpha: if progress > 0.0 { 1.0 } else { 0.0 },
+                opening: 1.0 - progress,
+                zoom: 1.0,
+            },
+            Self::DoorZoom => TransitionLook {
+                alpha: progress.powi(2),
+                opening: 0.0,
+                zoom: (DOOR_ZOOM - 1.0).mul_add(progress, 1.0),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIEW: Rect = Rect {
+        min: Vec2::new(-600.0, -400.0),
+        max: Vec2::new(600.0, 400.0),
+    };
+
+    fn area(rect: Rect) -> f32 {
+        rect.width() * rect.height()
+    }
+
+    #[test]
+    fn test_overlay_covers_the_view_around_the_hole() {
+        let center = Vec2::new(100.0, -50.0);
+        let radius = 80.0;
+        let covered: f32 = FadeOverlay::ALL
+            .into_iter()
+            .map(|piece| area(piece.rect(VIEW, center, radius)))
+            .sum();
+        assert!((covered - area(VIEW)).abs() < 0.01);
+        assert_eq!(
+            FadeOverlay::Hole.rect(VIEW, center, radius),
+            Rect::new(20.0, -130.0, 180.0, 30.0)
+        );
+
+        // a hole bigger than the view leaves nothing for the rest
+        for piece in [FadeOverlay::Top, FadeOverlay::Bottom] {
+            assert_eq!(area(piece.rect(VIEW, Vec2::ZERO, 1000.0)), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_transition_looks() {
+        for style in [
+            TransitionStyle::Fade,
+            TransitionStyle::Iris,
+            TransitionStyle::DoorZoom,
+        ] {
+            let start = style.look(0.0);
+            assert_eq!(start.zoom, 1.0);
+            assert!(start.alpha == 0.0 || start.opening == 1.0);
+            assert_eq!(style.look(1.0).alpha, 1.0);
+            assert_eq!(style.look(1.0).opening, 0.0);
+        }
+        assert_eq!(TransitionStyle::DoorZoom.look(1.0).zoom, DOOR_ZOOM);
+    }
 }
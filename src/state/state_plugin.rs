@@ -14,7 +14,7 @@ pub struct StatePlugin;
 impl Plugin for StatePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
-            .add_systems(Startup, setup_fade_overlay)
+            .add_systems(PostStartup, setup_fade_overlay) // once the camera is there
             .add_systems(Update, update_fade_overlay.in_set(InGameSet::Render))
             .add_systems(
                 Update,
@@ -3,48 +3,164 @@ use super::{
     state_component::{FadeEffect, FadeOverlay},
 };
 use crate::{
+    camera::camera_component::MainCamera,
     constants::{
-        CURTAIN_DURATION, FADE_IN_DURATION, FADE_OUT_DURATION, FADE_OVERLAY_Z, WINDOW_HEIGHT,
+        CAMERA_LAYER, CURTAIN_DURATION, FADE_IN_DURATION, FADE_OUT_DURATION, FADE_OVERLAY_Z,
     },
-    room::room_component::{CurrentFloorPlan, RoomState},
+    controls::Action,
+    player::Player,
+    room::room_component::CurrentFloorPlan,
+    settings::Settings,
 };
-use bevy::prelude::*;
+use bevy::{
+    image::ImageSampler,
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use leafwing_input_manager::prelude::ActionState;
+
+const IRIS_TEXTURE_SIZE: u32 = 256;
+const OVERLAY_MARGIN: f32 = 1.1; // of the view, so a resize never shows an edge for a frame
 
-pub fn setup_fade_overlay(mut commands: Commands, room_state: Res<RoomState>) {
-    commands.spawn((
-        Sprite {
-            color: Color::srgba(0.0, 0.0, 0.0, 0.0), // Fully transparent initially
-            custom_size: Some(Vec2::new(room_state.floor_ceiling_width, WINDOW_HEIGHT)), // Cover screen
-            ..default()
+/// black around a transparent circle, the iris drawn over the player
+fn iris_image() -> Image {
+    #[allow(clippy::cast_precision_loss)]
+    let size = IRIS_TEXTURE_SIZE as f32;
+    let mut data = Vec::new();
+    for y in 0..IRIS_TEXTURE_SIZE {
+        for x in 0..IRIS_TEXTURE_SIZE {
+            #[allow(clippy::cast_precision_loss)]
+            let offset = (Vec2::new(x as f32, y as f32) + 0.5) / size - 0.5;
+            // the edge is softened over a couple of pixels
+            let coverage = (offset.length() - 0.5).mul_add(size, 1.0).clamp(0.0, 1.0);
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            data.extend([0, 0, 0, (coverage * 255.0) as u8]);
+        }
+    }
+    let mut image = Image::new(
+        Extent3d {
+            width: IRIS_TEXTURE_SIZE,
+            height: IRIS_TEXTURE_SIZE,
+            depth_or_array_layers: 1,
         },
-        Transform::from_xyz(0.0, 0.0, FADE_OVERLAY_Z), // Render on top with a high z value
-        FadeOverlay,                                   // Add this tag so our query finds it
-    ));
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::linear(); // smooth scaled up, unlike the pixel art
+    image
+}
 
+/**
+ * the overlay hangs off the camera so it covers whatever the camera shows, wherever it is in the
+ * room and however big the window
+ */
+pub fn setup_fade_overlay(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    camera_query: Query<Entity, With<MainCamera>>,
+) {
     commands.insert_resource(FadeEffect {
         curtain: 0.0,
         alpha: 0.0,
         fading_out: false,
+        zoom: 1.0,
+    });
+
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+    let iris = images.add(iris_image());
+    commands.entity(camera).with_children(|builder| {
+        for piece in FadeOverlay::ALL {
+            builder.spawn((
+                Sprite {
+                    image: if piece == FadeOverlay::Hole {
+                        iris.clone()
+                    } else {
+                        Handle::default()
+                    },
+                    color: Color::srgba(0.0, 0.0, 0.0, 0.0), // Fully transparent initially
+                    custom_size: Some(Vec2::ZERO),
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 0.0, FADE_OVERLAY_Z - CAMERA_LAYER),
+                piece,
+            ));
+        }
     });
 }
 
+/**
+ * the overlay takes the look of the transition under way, the way out of a room until the next one
+ * starts to show
+ */
 pub fn update_fade_overlay(
-    room_state: Res<RoomState>,
-    mut query: Query<(&mut Sprite, &mut Transform), With<FadeOverlay>>,
+    mut fade: ResMut<FadeEffect>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut overlay_query: Query<(&FadeOverlay, &mut Sprite, &mut Transform), Without<MainCamera>>,
 ) {
-    if !room_state.is_changed() {
+    let style = if *state.get() == GameState::TransitioningIn {
+        settings.transitions.arriving
+    } else {
+        settings.transitions.leaving
+    };
+    let look = style.look(fade.alpha);
+    fade.zoom = look.zoom;
+
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
         return;
+    };
+    let view = Rect::from_center_size(Vec2::ZERO, projection.area.size() * OVERLAY_MARGIN);
+    let player = player_query.get_single().map_or(Vec2::ZERO, |transform| {
+        (transform.translation - camera_transform.translation).truncate()
+    });
+    let farthest = [
+        view.min,
+        view.max,
+        Vec2::new(view.min.x, view.max.y),
+        Vec2::new(view.max.x, view.min.y),
+    ]
+    .into_iter()
+    .map(|corner| corner.distance(player))
+    .fold(0.0, f32::max);
+    let radius = farthest * look.opening;
+    // a closed hole goes below the view so one piece covers all of it without a seam
+    let center = if radius > 0.0 {
+        player
+    } else {
+        Vec2::new(0.0, view.min.y)
+    };
+
+    for (piece, mut sprite, mut transform) in &mut overlay_query {
+        let rect = piece.rect(view, center, radius);
+        sprite.color.set_alpha(look.alpha);
+        sprite.custom_size = Some(rect.size());
+        transform.translation.x = rect.center().x;
+        transform.translation.y = rect.center().y;
     }
-    for (mut sprite, mut transform) in &mut query {
-        sprite.custom_size = Some(Vec2::new(room_state.floor_ceiling_width, WINDOW_HEIGHT));
-        transform.translation.z = FADE_OVERLAY_Z;
-    }
+}
+
+/// how many times faster than usual the transition runs this frame, the walk through the doors too
+pub fn transition_speed(settings: &Settings, action_query: &Query<&ActionState<Action>>) -> f32 {
+    let skipping = action_query
+        .iter()
+        .any(|action_state| action_state.pressed(&Action::Skip));
+    settings.transitions.speed(skipping)
 }
 
 pub fn fade_out(
     mut next_state: ResMut<NextState<GameState>>,
     mut fade: ResMut<FadeEffect>,
-    mut fade_query: Query<&mut Sprite, With<FadeOverlay>>,
+    settings: Res<Settings>,
+    action_query: Query<&ActionState<Action>>,
     time: Res<Time>,
     current_floor_plan: Res<CurrentFloorPlan>,
 ) {
@@ -59,12 +175,10 @@ pub fn fade_out(
         return;
     }
 
-    let mut sprite = fade_query.single_mut();
-
     if fade.fading_out {
-        fade.alpha += time.delta_secs() / FADE_OUT_DURATION; // Slow fade-out
+        let speed = transition_speed(&settings, &action_query);
+        fade.alpha += time.delta_secs() * speed / FADE_OUT_DURATION; // Slow fade-out
         fade.alpha = fade.alpha.min(1.0); // Clamp at full opacity
-        sprite.color.set_alpha(fade.alpha);
 
         if fade.alpha >= 1.0 {
             debug!("Fade out complete");
@@ -78,9 +192,12 @@ pub fn fade_out(
 pub fn room_change_curtain(
     mut next_state: ResMut<NextState<GameState>>,
     mut fade: ResMut<FadeEffect>,
+    settings: Res<Settings>,
+    action_query: Query<&ActionState<Action>>,
     time: Res<Time>,
 ) {
-    fade.curtain += time.delta_secs() * CURTAIN_DURATION; // Slow fade-out
+    let speed = transition_speed(&settings, &action_query);
+    fade.curtain += time.delta_secs() * speed * CURTAIN_DURATION; // Slow fade-out
     fade.curtain = fade.curtain.min(1.0); // Clamp at full opacity
 
     if fade.curtain >= 1.0 {
@@ -93,19 +210,18 @@ pub fn room_change_curtain(
 pub fn fade_in(
     mut next_state: ResMut<NextState<GameState>>,
     mut fade: ResMut<FadeEffect>,
-    mut fade_query: Query<&mut Sprite, With<FadeOverlay>>,
+    settings: Res<Settings>,
+    action_query: Query<&ActionState<Action>>,
     time: Res<Time>,
 ) {
-    let mut sprite = fade_query.single_mut();
-
     if !fade.fading_out {
-        fade.alpha -= time.delta_secs() / FADE_IN_DURATION; // Slow fade-in
+        let speed = transition_speed(&settings, &action_query);
+        fade.alpha -= time.delta_secs() * speed / FADE_IN_DURATION; // Slow fade-in
         fade.alpha = fade.alpha.max(0.0); // Clamp at full transparency
-        sprite.color.set_alpha(fade.alpha);
 
         if fade.alpha <= 0.0 {
             debug!("Fade in complete");
-            next_state.set(GameState::InGame); // Transition complete
+            next_state.set(GameState::LeavingDoor);
         }
     }
 }
@@ -0,0 +1,6 @@
+pub mod terminal_component;
+pub mod terminal_plugin;
+pub mod terminal_systems;
+
+pub use terminal_component::{Terminal, TerminalOpened};
+pub use terminal_plugin::TerminalPlugin;
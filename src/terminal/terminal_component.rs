@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::floorplan::RoomResource;
+
+const MAX_TERMINAL_LINES: usize = 500; // older lines scroll off the top for good
+
+#[derive(Debug, Component)]
+pub struct TerminalPanel;
+
+#[derive(Debug, Component)]
+pub struct TerminalText;
+
+/**
+ * the in-room terminal - it is open while it shows a resource, and whichever integration knows about
+ * that resource streams lines into it
+ */
+#[derive(Debug, Default, Resource)]
+pub struct Terminal {
+    pub resource: Option<RoomResource>,
+    pub lines: VecDeque<String>,
+    pub scroll: usize, // how many lines we are scrolled back from the newest one
+}
+
+impl Terminal {
+    pub fn open(&mut self, resource: RoomResource) {
+        self.lines.clear();
+        self.scroll = 0;
+        self.push(format!(
+            "{} {} in {}",
+            resource.kind,
+            resource.name,
+            resource.namespace.as_deref().unwrap_or("cluster")
+        ));
+        self.resource = Some(resource);
+    }
+
+    pub fn close(&mut self) {
+        self.resource = None;
+        self.lines.clear();
+        self.scroll = 0;
+    }
+
+    pub const fn is_open(&self) -> bool {
+        self.resource.is_some()
+    }
+
+    /**
+     * only lines for the resource on screen are kept, a slow task for a room we already left can't
+     * write into the next one
+     */
+    pub fn push_for(&mut self, resource: &RoomResource, line: String) {
+        if self.resource.as_ref() == Some(resource) {
+            self.push(line);
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        self.lines.push_back(line);
+        if self.lines.len() > MAX_TERMINAL_LINES {
+            self.lines.pop_front();
+        }
+    }
+}
+
+/// sent when the player opens the terminal in a room that stands for a real-world resource
+#[derive(Event)]
+pub struct TerminalOpened {
+    pub resource: RoomResource,
+}
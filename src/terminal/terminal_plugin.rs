@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use super::terminal_component::{Terminal, TerminalOpened};
+use super::terminal_systems::{
+    close_terminal, fit_terminal, render_terminal, scroll_terminal, toggle_terminal,
+};
+use crate::{
+    schedule::InGameSet,
+    state::GameState::{InGame, RoomChange},
+};
+
+pub struct TerminalPlugin;
+
+impl Plugin for TerminalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Terminal>()
+            .add_event::<TerminalOpened>()
+            .add_systems(
+                Update,
+                (toggle_terminal, scroll_terminal)
+                    .in_set(InGameSet::Update)
+                    .run_if(in_state(InGame)),
+            )
+            .add_systems(
+                Update,
+                (render_terminal, fit_terminal)
+                    .chain()
+                    .in_set(InGameSet::Render),
+            )
+            .add_systems(OnEnter(RoomChange), close_terminal);
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::color::Color;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::text::TextBounds;
+use leafwing_input_manager::prelude::ActionState;
+
+use super::terminal_component::{Terminal, TerminalOpened, TerminalPanel, TerminalText};
+use crate::camera::camera_component::MainCamera;
+use crate::constants::{CAMERA_LAYER, TERMINAL_LAYER};
+use crate::controls::Action;
+use crate::room::room_component::RoomState;
+
+static TERMINAL_BACKGROUND_COLOR: Color = Color::srgba(0.02, 0.05, 0.02, 0.9);
+static TERMINAL_TEXT_COLOR: Color = Color::srgb(0.4, 1.0, 0.4); // old school green
+const TERMINAL_VISIBLE_LINES: usize = 16;
+const TERMINAL_LINE_CHARS: usize = 110; // long log lines are cut rather than wrapped off the panel
+const TERMINAL_FONT_SIZE: f32 = 14.0;
+const TERMINAL_PADDING: f32 = 10.0;
+
+/**
+ * 'I' to inspect the room you are in - only rooms that stand for a real-world resource have anything
+ * to show
+ */
+pub fn toggle_terminal(
+    action_query: Query<&ActionState<Action>>,
+    room_state: Res<RoomState>,
+    mut terminal: ResMut<Terminal>,
+    mut opened: EventWriter<TerminalOpened>,
+) {
+    if !action_query
+        .iter()
+        .any(|action_state| action_state.just_pressed(&Action::Inspect))
+    {
+        return;
+    }
+    if terminal.is_open() {
+        terminal.close();
+    } else if let Some(resource) = room_state.resource.clone() {
+        terminal.open(resource.clone());
+        opened.send(TerminalOpened { resource });
+    }
+}
+
+pub fn scroll_terminal(action_query: Query<&ActionState<Action>>, mut terminal: ResMut<Terminal>) {
+    if !terminal.is_open() {
+        return;
+    }
+    let just_pressed = |action: Action| {
+        action_query
+            .iter()
+            .any(|action_state| action_state.just_pressed(&action))
+    };
+    let max_scroll = terminal.lines.len().saturating_sub(TERMINAL_VISIBLE_LINES);
+    if just_pressed(Action::ScrollUp) {
+        terminal.scroll = (terminal.scroll + TERMINAL_VISIBLE_LINES / 2).min(max_scroll);
+    } else if just_pressed(Action::ScrollDown) {
+        terminal.scroll = terminal.scroll.saturating_sub(TERMINAL_VISIBLE_LINES / 2);
+    }
+}
+
+pub fn close_terminal(mut terminal: ResMut<Terminal>) {
+    terminal.close();
+}
+
+/**
+* the lines that fit on the panel, newest at the bottom unless the player scrolled back
+*/
+fn visible_text(terminal: &Terminal) -> String {
+    let end = terminal.lines.len().saturating_sub(terminal.scroll);
+    let start = end.saturating_sub(TERMINAL_VISIBLE_LINES);
+    terminal
+        .lines
+        .range(start..end)
+        .map(|line| line.chars().take(TERMINAL_LINE_CHARS).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/**
+* the panel's size on screen and where it hangs off the camera - scaled by the camera's zoom so it
+* is drawn the same size however far the camera is zoomed
+*/
+fn panel_layout(projection: &OrthographicProjection) -> (Vec2, Transform) {
+    let view = projection.area.size() / projection.scale;
+    let box_size = Vec2::new(view.x * 0.8, view.y * 0.4);
+    let transform = Transform::from_xyz(
+        0.0,
+        -view.y * 0.2 * projection.scale,
+        TERMINAL_LAYER - CAMERA_LAYER,
+    )
+    .with_scale(Vec3::splat(projection.scale));
+    (box_size, transform)
+}
+
+/**
+* the text fills the panel inside its padding, from the bottom left corner
+*/
+fn text_layout(box_size: Vec2) -> (TextBounds, Transform) {
+    (
+        TextBounds::from(box_size - Vec2::splat(TERMINAL_PADDING * 2.0)),
+        Transform::from_xyz(
+            -box_size.x / 2.0 + TERMINAL_PADDING,
+            -box_size.y / 2.0 + TERMINAL_PADDING,
+            1.0,
+        ),
+    )
+}
+
+/**
+* the panel hangs off the camera so it stays on screen while the player walks around the room, sized
+* to the window
+*/
+pub fn render_terminal(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    terminal: Res<Terminal>,
+    camera_query: Query<(Entity, &OrthographicProjection), With<MainCamera>>,
+    panel_query: Query<Entity, With<TerminalPanel>>,
+    mut text_query: Query<&mut Text2d, With<TerminalText>>,
+) {
+    if !terminal.is_changed() {
+        return;
+    }
+    if !terminal.is_open() {
+        for entity in panel_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    if !panel_query.is_empty() {
+        for mut text in &mut text_query {
+            text.0 = visible_text(&terminal);
+        }
+        return;
+    }
+    let Ok((camera, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let (box_size, panel_transform) = panel_layout(projection);
+    let (text_bounds, text_transform) = text_layout(box_size);
+    let text_font = TextFont {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: TERMINAL_FONT_SIZE,
+        ..default()
+    };
+    let panel = commands
+        .spawn((
+            Sprite::from_color(TERMINAL_BACKGROUND_COLOR, box_size),
+            panel_transform,
+            TerminalPanel,
+        ))
+        .with_children(|builder| {
+            builder.spawn((
+                Text2d::new(visible_text(&terminal)),
+                text_font,
+                TextColor(TERMINAL_TEXT_COLOR),
+                TextLayout::new(JustifyText::Left, LineBreak::NoWrap),
+                text_bounds,
+                Anchor::BottomLeft,
+                text_transform,
+                TerminalText,
+            ));
+        })
+        .id();
+    commands.entity(camera).add_child(panel);
+}
+
+/**
+* an open panel follows the window as it is resized and the camera as it zooms
+*/
+#[allow(clippy::type_complexity)]
+pub fn fit_terminal(
+    camera_query: Query<
+        &OrthographicProjection,
+        (With<MainCamera>, Changed<OrthographicProjection>),
+    >,
+    mut panel_query: Query<(&mut Transform, &mut Sprite), With<TerminalPanel>>,
+    mut text_query: Query<
+        (&mut Transform, &mut TextBounds),
+        (With<TerminalText>, Without<TerminalPanel>),
+    >,
+) {
+    let Ok(projection) = camera_query.get_single() else {
+        return;
+    };
+    let (box_size, panel_transform) = panel_layout(projection);
+    for (mut transform, mut sprite) in &mut panel_query {
+        *transform = panel_transform;
+        sprite.custom_size = Some(box_size);
+    }
+    for (mut transform, mut bounds) in &mut text_query {
+        (*bounds, *transform) = text_layout(box_size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panel_follows_the_window_size() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, fit_terminal);
+        let mut projection = OrthographicProjection::default_2d();
+        projection.area = Rect::new(-500.0, -400.0, 500.0, 400.0);
+        let camera = app.world_mut().spawn((MainCamera, projection)).id();
+        let panel = app
+            .world_mut()
+            .spawn((
+                Sprite::from_color(TERMINAL_BACKGROUND_COLOR, Vec2::ONE),
+                Transform::default(),
+                TerminalPanel,
+            ))
+            .with_children(|builder| {
+                builder.spawn((TextBounds::default(), Transform::default(), TerminalText));
+            })
+            .id();
+
+        app.update();
+        assert_eq!(
+            app.world().get::<Sprite>(panel).unwrap().custom_size,
+            Some(Vec2::new(800.0, 320.0))
+        );
+
+        let mut projection = app
+            .world_mut()
+            .get_mut::<OrthographicProjection>(camera)
+            .unwrap();
+        projection.area = Rect::new(-1000.0, -400.0, 1000.0, 400.0);
+        projection.scale = 2.0;
+        app.update();
+
+        let transform = app.world().get::<Transform>(panel).unwrap();
+        assert_eq!(transform.scale, Vec3::splat(2.0));
+        assert_eq!(
+            app.world().get::<Sprite>(panel).unwrap().custom_size,
+            Some(Vec2::new(800.0, 160.0))
+        );
+    }
+}